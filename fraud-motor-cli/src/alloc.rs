@@ -0,0 +1,21 @@
+use crate::state::{self, State};
+use clap::Parser;
+use fraud_motor_core::process::Permissions;
+use std::error::Error;
+
+#[derive(Parser)]
+pub struct Args {
+    #[arg(value_parser = state::parse_size)]
+    size: usize,
+    #[arg(default_value = "rwx")]
+    perms: Permissions,
+}
+
+pub fn alloc(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
+    match state.proc.alloc(args.size, &args.perms) {
+        Ok(addr) => println!("{:016x}", addr),
+        Err(err) => println!("alloc: {}", err),
+    }
+
+    Ok(())
+}
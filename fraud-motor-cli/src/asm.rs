@@ -0,0 +1,53 @@
+use crate::state::State;
+use clap::Parser;
+use fraud_motor_core::asm;
+use std::error::Error;
+
+#[derive(Parser)]
+pub struct Args {
+    addr: String,
+    /// Instructions separated by ';'
+    #[arg(required = true, trailing_var_arg = true, allow_hyphen_values = true)]
+    text: Vec<String>,
+    /// Write the assembled bytes to memory
+    #[arg(long)]
+    write: bool,
+    /// Add the assembled bytes as a named patch instead of writing them
+    #[arg(long, conflicts_with = "write")]
+    patch: Option<String>,
+}
+
+pub fn asm(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
+    let Some(addr) = state.try_resolve(&args.addr) else {
+        return Ok(());
+    };
+
+    let text = args.text.join(" ");
+    let text = text.trim_matches('"');
+    let bytes = match asm::assemble_with(text, addr, |name| state.resolve(name).ok()) {
+        Ok(bytes) => bytes,
+        Err(err) => {
+            println!("{}", err);
+
+            return Ok(());
+        }
+    };
+
+    let hex: String = bytes.iter().map(|byte| format!("{:02x}", byte)).collect();
+
+    println!("{:016x} {}", addr, hex);
+
+    let result = if let Some(name) = &args.patch {
+        state.patches.add(name, addr, &bytes, None)
+    } else if args.write {
+        state.memory.write(&bytes, addr)
+    } else {
+        Ok(())
+    };
+
+    if let Err(err) = result {
+        println!("{}: {}", args.addr, err);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,134 @@
+use crate::state::State;
+use clap::{Parser, Subcommand};
+use fraud_motor_core::thread::Registers;
+use std::error::Error;
+
+#[derive(Parser)]
+pub struct Args {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    Add {
+        addr: String,
+        #[arg(long)]
+        auto_continue: bool,
+    },
+    Del {
+        addr: String,
+    },
+    List,
+    Log {
+        addr: String,
+        #[arg(long)]
+        count: Option<usize>,
+    },
+    Cont,
+}
+
+pub fn breakpoint(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
+    match args.command {
+        Commands::Add {
+            addr,
+            auto_continue,
+        } => {
+            if let Some(addr_value) = state.try_resolve(&addr) {
+                if let Err(err) = state
+                    .debugger()
+                    .and_then(|debugger| Ok(debugger.add_breakpoint(addr_value, auto_continue)?))
+                {
+                    println!("{}: {}", addr, err);
+                }
+            }
+        }
+        Commands::Del { addr } => {
+            if let Some(addr_value) = state.try_resolve(&addr) {
+                if let Err(err) = state
+                    .debugger()
+                    .and_then(|debugger| Ok(debugger.remove_breakpoint(addr_value)?))
+                {
+                    println!("{}: {}", addr, err);
+                }
+            }
+        }
+        Commands::List => {
+            let Some(debugger) = &state.debugger else {
+                return Ok(());
+            };
+
+            let breakpoints = match debugger.breakpoints() {
+                Ok(breakpoints) => breakpoints,
+                Err(err) => {
+                    println!("breakpoint: {}", err);
+
+                    return Ok(());
+                }
+            };
+
+            for breakpoint in breakpoints {
+                println!(
+                    "{:016x} {:>8}{}{}",
+                    breakpoint.addr(),
+                    breakpoint.hits(),
+                    if breakpoint.auto_continue() {
+                        " auto"
+                    } else {
+                        ""
+                    },
+                    state.locate(breakpoint.addr()),
+                );
+            }
+        }
+        Commands::Log { addr, count } => {
+            let Some(addr_value) = state.try_resolve(&addr) else {
+                return Ok(());
+            };
+
+            let breakpoints = match state
+                .debugger()
+                .and_then(|debugger| Ok(debugger.breakpoints()?))
+            {
+                Ok(breakpoints) => breakpoints,
+                Err(err) => {
+                    println!("{}: {}", addr, err);
+
+                    return Ok(());
+                }
+            };
+
+            let breakpoint = breakpoints
+                .into_iter()
+                .find(|breakpoint| breakpoint.addr() == addr_value);
+
+            let Some(breakpoint) = breakpoint else {
+                println!("{}: breakpoint not found", addr);
+
+                return Ok(());
+            };
+
+            let log = breakpoint.log();
+            let skip = log.len() - count.unwrap_or(log.len()).min(log.len());
+
+            for hit in log.iter().skip(skip) {
+                print!("{:>8}", hit.thread());
+
+                if let Some(registers) = hit.registers() {
+                    for name in Registers::NAMES {
+                        print!(" {}={:x}", name, registers.get(name).unwrap());
+                    }
+                }
+
+                println!();
+            }
+        }
+        Commands::Cont => {
+            if let Err(err) = state.debugger().and_then(|debugger| Ok(debugger.resume()?)) {
+                println!("cont: {}", err);
+            }
+        }
+    };
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use crate::state::State;
+use clap::Parser;
+use std::error::Error;
+
+#[derive(Parser)]
+pub struct Args {
+    addr: String,
+    #[arg(allow_hyphen_values = true)]
+    args: Vec<String>,
+}
+
+pub fn call(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
+    let Some(addr) = state.try_resolve(&args.addr) else {
+        return Ok(());
+    };
+
+    let mut values = Vec::new();
+
+    for arg in &args.args {
+        let value = match arg.parse::<i64>() {
+            Ok(value) => value as u64,
+            Err(_) => match state.try_resolve(arg) {
+                Some(value) => value as u64,
+                None => return Ok(()),
+            },
+        };
+
+        values.push(value);
+    }
+
+    match state.proc.call(addr, &values) {
+        Ok(result) => println!("{:016x} {}", result, result as i64),
+        Err(err) => println!("{}: {}", args.addr, err),
+    }
+
+    Ok(())
+}
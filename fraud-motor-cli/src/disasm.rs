@@ -0,0 +1,61 @@
+use crate::state::State;
+use clap::Parser;
+use fraud_motor_core::disasm::Disassembler;
+use std::error::Error;
+
+#[derive(Parser)]
+pub struct Args {
+    addr: String,
+    #[arg(default_value_t = 16)]
+    count: usize,
+    /// Disassemble from a named dump instead of live memory
+    #[arg(long)]
+    dump: Option<String>,
+}
+
+pub fn disasm(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
+    let Some(addr) = state.try_resolve(&args.addr) else {
+        return Ok(());
+    };
+
+    let modules: Vec<_> = state.proc.modules()?.filter_map(Result::ok).collect();
+    let disasm = Disassembler::new(&modules, &state.symbols);
+    let instructions = match &args.dump {
+        Some(name) => match state.dumps.get(name) {
+            Some(dump) => disasm.read_dump(dump, addr, args.count),
+            None => {
+                println!("{}: dump not found", name);
+
+                return Ok(());
+            }
+        },
+        None => disasm.read(&state.memory, addr, args.count),
+    };
+
+    match instructions {
+        Ok(instructions) => {
+            for instruction in instructions {
+                let bytes: String = instruction
+                    .bytes()
+                    .iter()
+                    .map(|byte| format!("{:02x}", byte))
+                    .collect();
+
+                print!(
+                    "{:016x} {:<24} {}",
+                    instruction.addr(),
+                    bytes,
+                    instruction.text()
+                );
+
+                match instruction.annotation() {
+                    Some(annotation) => println!(" ; {}", annotation),
+                    None => println!(),
+                }
+            }
+        }
+        Err(err) => println!("{}: {}", args.addr, err),
+    }
+
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use crate::state::State;
 use clap::{Parser, Subcommand};
 use fraud_motor_core::dump::ProcessDump;
+use fraud_motor_core::process::Region;
 use std::error::Error;
 
 #[derive(Parser)]
@@ -11,19 +12,35 @@ pub struct Args {
 
 #[derive(Subcommand)]
 enum Commands {
-    New { name: String },
-    Drop { name: String },
-    Info { name: Option<String> },
+    New {
+        name: String,
+        #[arg(long)]
+        freeze: bool,
+    },
+    Drop {
+        name: String,
+    },
+    Info {
+        name: Option<String>,
+    },
 }
 
 pub fn dump(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
     match args.command {
-        Commands::New { name } => {
-            let dump = ProcessDump::new(&state.memory, &state.proc, |region| {
-                region.permissions().write()
-            })?;
+        Commands::New { name, freeze } => {
+            let filter = |region: &Region| region.permissions().write();
+            let dump = if freeze {
+                ProcessDump::frozen(&state.memory, &state.proc, filter)
+            } else {
+                ProcessDump::new(&state.memory, &state.proc, filter)
+            };
 
-            state.dumps.insert(name, dump);
+            match dump {
+                Ok(dump) => {
+                    state.dumps.insert(name, dump);
+                }
+                Err(err) => println!("{}: {}", name, err),
+            }
         }
         Commands::Drop { name } => {
             state.dumps.remove(&name);
@@ -35,20 +52,27 @@ pub fn dump(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
                         let perms = region.permissions();
 
                         print!(
-                            "{} {:016x}-{:016x} {}{}{}",
-                            if data.is_ok() { "ok " } else { "err" },
+                            "{} {:016x}-{:016x} {}{}{}{} {:08x} {:?}",
+                            match data {
+                                Ok(data) if data.complete() => "ok ",
+                                Ok(_) => "hol",
+                                Err(_) => "err",
+                            },
                             region.start(),
                             region.end(),
                             if perms.read() { "r" } else { "-" },
                             if perms.write() { "w" } else { "-" },
                             if perms.exec() { "x" } else { "-" },
+                            if region.shared() { "s" } else { "p" },
+                            region.offset(),
+                            region.kind(),
                         );
 
-                        if let Some(path) = region.path() {
-                            println!(" {}", path.display());
-                        } else {
-                            println!();
+                        if let Some(name) = region.name() {
+                            print!(" {}", name);
                         }
+
+                        println!("{}", state.describe(region.start()));
                     }
                 } else {
                     println!("{}: dump not found", name);
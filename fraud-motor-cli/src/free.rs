@@ -0,0 +1,22 @@
+use crate::state::{self, State};
+use clap::Parser;
+use std::error::Error;
+
+#[derive(Parser)]
+pub struct Args {
+    addr: String,
+    #[arg(value_parser = state::parse_size)]
+    size: usize,
+}
+
+pub fn free(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
+    let Some(addr) = state.try_resolve(&args.addr) else {
+        return Ok(());
+    };
+
+    if let Err(err) = state.proc.free(addr, args.size) {
+        println!("{}: {}", args.addr, err);
+    }
+
+    Ok(())
+}
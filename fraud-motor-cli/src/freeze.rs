@@ -0,0 +1,159 @@
+use crate::state::State;
+use clap::{Parser, Subcommand};
+use fraud_motor_core::freeze::Mode;
+use fraud_motor_core::value::Type;
+use std::error::Error;
+use std::time::Duration;
+
+#[derive(Parser)]
+pub struct Args {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    Add {
+        addr: String,
+        ty: Type,
+        value: String,
+        /// Read and write the value as big-endian
+        #[arg(long)]
+        be: bool,
+        /// Only write the value while the current value is below this threshold
+        #[arg(long, conflicts_with_all = ["above", "increment", "decrement"])]
+        below: Option<String>,
+        /// Only write the value while the current value is above this threshold
+        #[arg(long, conflicts_with_all = ["increment", "decrement"])]
+        above: Option<String>,
+        /// Allow the value to increase, but never decrease
+        #[arg(long, conflicts_with = "decrement")]
+        increment: bool,
+        /// Allow the value to decrease, but never increase
+        #[arg(long)]
+        decrement: bool,
+    },
+    Del {
+        addr: String,
+    },
+    List,
+    /// Toggle all freezes, or only the one at the given address
+    Pause {
+        addr: Option<String>,
+    },
+    /// Set the interval between writes in milliseconds
+    Interval {
+        millis: u64,
+    },
+}
+
+pub fn freeze(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
+    match args.command {
+        Commands::Add {
+            addr,
+            ty,
+            value,
+            be,
+            below,
+            above,
+            increment,
+            decrement,
+        } => {
+            let Some(addr) = state.try_resolve(&addr) else {
+                return Ok(());
+            };
+
+            let result = ty.parse(&value).and_then(|value| {
+                let mode = if let Some(below) = below {
+                    Mode::Below(ty.parse(&below)?)
+                } else if let Some(above) = above {
+                    Mode::Above(ty.parse(&above)?)
+                } else if increment {
+                    Mode::Increment
+                } else if decrement {
+                    Mode::Decrement
+                } else {
+                    Mode::Always
+                };
+
+                state.freezer.add(addr, value, mode, be)
+            });
+
+            if let Err(err) = result {
+                println!("{:016x}: {}", addr, err);
+            }
+        }
+        Commands::Del { addr } => {
+            let Some(addr) = state.try_resolve(&addr) else {
+                return Ok(());
+            };
+
+            if let Err(err) = state.freezer.remove(addr) {
+                println!("{:016x}: {}", addr, err);
+            }
+        }
+        Commands::List => {
+            if state.freezer.paused() {
+                println!("paused");
+            }
+
+            for (addr, freeze) in state.freezer.entries() {
+                let mode = match freeze.mode() {
+                    Mode::Always => String::new(),
+                    Mode::Below(threshold) => format!(" below {}", threshold),
+                    Mode::Above(threshold) => format!(" above {}", threshold),
+                    Mode::Increment => " increment".to_owned(),
+                    Mode::Decrement => " decrement".to_owned(),
+                };
+
+                println!(
+                    "{} {:016x} {}{} {}{}{}",
+                    if freeze.paused() { "-" } else { "+" },
+                    addr,
+                    freeze.value().ty(),
+                    if freeze.big_endian() { "be" } else { "" },
+                    freeze.value(),
+                    mode,
+                    state.locate(addr),
+                );
+            }
+        }
+        Commands::Pause { addr: None } => {
+            let paused = !state.freezer.paused();
+
+            state.freezer.set_paused(paused);
+            println!("{}", if paused { "paused" } else { "resumed" });
+        }
+        Commands::Pause { addr: Some(addr) } => {
+            let Some(addr) = state.try_resolve(&addr) else {
+                return Ok(());
+            };
+
+            let result = state.freezer.get(addr).map(|freeze| {
+                state
+                    .freezer
+                    .set_entry_paused(addr, !freeze.paused())
+                    .map(|_| !freeze.paused())
+            });
+
+            match result {
+                Some(Ok(paused)) => {
+                    println!(
+                        "{:016x}: {}",
+                        addr,
+                        if paused { "paused" } else { "resumed" }
+                    )
+                }
+                Some(Err(err)) => println!("{:016x}: {}", addr, err),
+                None => println!("{:016x}: address is not frozen", addr),
+            }
+        }
+        Commands::Interval { millis } => {
+            if let Err(err) = state.freezer.set_interval(Duration::from_millis(millis)) {
+                println!("{}: {}", millis, err);
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,74 @@
+use crate::state::State;
+use clap::{Parser, Subcommand};
+use fraud_motor_core::hook::Hook;
+use std::error::Error;
+
+#[derive(Parser)]
+pub struct Args {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    Add {
+        name: String,
+        target: String,
+        detour: String,
+    },
+    Del {
+        name: String,
+    },
+    List,
+}
+
+pub fn hook(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
+    match args.command {
+        Commands::Add {
+            name,
+            target,
+            detour,
+        } => {
+            if state.hooks.contains_key(&name) {
+                println!("{}: hook already exists", name);
+                return Ok(());
+            }
+
+            let (Some(target), Some(detour)) =
+                (state.try_resolve(&target), state.try_resolve(&detour))
+            else {
+                return Ok(());
+            };
+
+            match Hook::install(&state.memory, &state.proc, target, detour) {
+                Ok(hook) => {
+                    println!("{:016x}", hook.trampoline());
+                    state.hooks.insert(name, hook);
+                }
+                Err(err) => println!("{}: {}", name, err),
+            }
+        }
+        Commands::Del { name } => match state.hooks.remove(&name) {
+            Some(hook) => {
+                if let Err(err) = hook.uninstall(&state.proc) {
+                    println!("{}: {}", name, err);
+                }
+            }
+            None => println!("{}: hook not found", name),
+        },
+        Commands::List => {
+            for (name, hook) in &state.hooks {
+                println!(
+                    "{:016x} {} -> {:016x} trampoline {:016x}{}",
+                    hook.target(),
+                    name,
+                    hook.detour(),
+                    hook.trampoline(),
+                    state.locate(hook.target()),
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
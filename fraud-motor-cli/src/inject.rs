@@ -0,0 +1,57 @@
+use crate::state::State;
+use clap::Parser;
+use fraud_motor_core::symbol::Symbols;
+use std::error::Error;
+use std::fs;
+
+#[derive(Parser)]
+pub struct Args {
+    path: String,
+    #[arg(long)]
+    unload: bool,
+}
+
+pub fn inject(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
+    if args.unload {
+        let path = fs::canonicalize(&args.path).unwrap_or_else(|_| args.path.clone().into());
+        let Some(index) = state
+            .libraries
+            .iter()
+            .position(|library| library.module().path() == path || library.module().is(&args.path))
+        else {
+            println!("{}: library not injected", args.path);
+
+            return Ok(());
+        };
+
+        let library = state.libraries.remove(index);
+
+        if let Err(err) = state.proc.unload_library(library) {
+            println!("{}: {}", args.path, err);
+        }
+    } else {
+        match state.proc.inject_library(&args.path) {
+            Ok(library) => {
+                let module = library.module();
+
+                println!(
+                    "{:016x}-{:016x} {:8x} {}",
+                    module.base(),
+                    module.end(),
+                    module.size(),
+                    module.path().display(),
+                );
+
+                state.libraries.push(library);
+            }
+            Err(err) => println!("{}: {}", args.path, err),
+        }
+    }
+
+    match Symbols::load(&state.proc) {
+        Ok(symbols) => state.symbols = symbols,
+        Err(err) => println!("symbols: {}", err),
+    }
+
+    Ok(())
+}
@@ -1,22 +1,105 @@
+pub mod alloc;
+pub mod asm;
+pub mod breakpoint;
+pub mod call;
+pub mod disasm;
 pub mod dump;
+pub mod free;
+pub mod freeze;
+pub mod hook;
+pub mod inject;
+pub mod modules;
+pub mod patch;
+pub mod pointer;
+pub mod protect;
+pub mod regs;
 pub mod scan;
 pub mod state;
+pub mod symbol;
+pub mod threads;
+pub mod watch;
 pub mod write;
 
 use clap::Parser;
+use fraud_motor_core::memory::Backend;
+use fraud_motor_core::process::{self, Process, ProcessInfo};
+use regex::Regex;
 use rustyline::DefaultEditor;
 use state::State;
 use std::error::Error;
 
 #[derive(Parser)]
 struct Args {
-    pid: u32,
+    /// Process id, or a regex matched against process names (or command lines)
+    process: String,
+    /// Access memory with process_vm_readv/writev instead of /proc/<pid>/mem
+    #[arg(long)]
+    process_vm: bool,
+}
+
+fn find_process(pattern: &str) -> Result<u32, Box<dyn Error>> {
+    if let Ok(pid) = pattern.parse() {
+        return Ok(pid);
+    }
+
+    let regex = Regex::new(pattern)?;
+    let mut infos = Vec::new();
+
+    for id in process::list()? {
+        let id = id?;
+
+        if id == std::process::id() {
+            continue;
+        }
+
+        if let Ok(info) = Process::open(id).and_then(|proc| proc.info()) {
+            infos.push(info);
+        }
+    }
+
+    let mut matches: Vec<_> = infos
+        .iter()
+        .filter(|info| regex.is_match(info.name()))
+        .collect();
+
+    if matches.is_empty() {
+        matches = infos
+            .iter()
+            .filter(|info| regex.is_match(&info.cmdline().join(" ")))
+            .collect();
+    }
+
+    match matches.as_slice() {
+        [] => Err(format!("{}: no matching process", pattern).into()),
+        [info] => Ok(info.id()),
+        matches => {
+            matches.iter().for_each(|info| print_process(info));
+
+            Err(format!("{}: {} matching processes", pattern, matches.len()).into())
+        }
+    }
+}
+
+fn print_process(info: &ProcessInfo) {
+    println!(
+        "{:>8} {:>8} {:?} {:<16} {}",
+        info.id(),
+        info.parent_id(),
+        info.state(),
+        info.name(),
+        info.cmdline().join(" "),
+    );
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
     let args = Args::parse();
     let mut rl = DefaultEditor::new()?;
-    let mut state = State::new(args.pid)?;
+    let backend = if args.process_vm {
+        Backend::ProcessVm
+    } else {
+        Backend::ProcMem
+    };
+    let mut state = State::new(find_process(&args.process)?, backend)?;
 
     loop {
         match rl.readline("(fm) ") {
@@ -25,14 +108,82 @@ fn main() -> Result<(), Box<dyn Error>> {
 
                 match cmd.first() {
                     Some(&"exit") => break,
+                    Some(&"alloc") => match alloc::Args::try_parse_from(&cmd) {
+                        Ok(args) => alloc::alloc(&mut state, args)?,
+                        Err(err) => err.print()?,
+                    },
+                    Some(&"asm") => match asm::Args::try_parse_from(&cmd) {
+                        Ok(args) => asm::asm(&mut state, args)?,
+                        Err(err) => err.print()?,
+                    },
+                    Some(&"break") => match breakpoint::Args::try_parse_from(&cmd) {
+                        Ok(args) => breakpoint::breakpoint(&mut state, args)?,
+                        Err(err) => err.print()?,
+                    },
+                    Some(&"call") => match call::Args::try_parse_from(&cmd) {
+                        Ok(args) => call::call(&mut state, args)?,
+                        Err(err) => err.print()?,
+                    },
+                    Some(&"disasm") => match disasm::Args::try_parse_from(&cmd) {
+                        Ok(args) => disasm::disasm(&mut state, args)?,
+                        Err(err) => err.print()?,
+                    },
                     Some(&"dump") => match dump::Args::try_parse_from(&cmd) {
                         Ok(args) => dump::dump(&mut state, args)?,
                         Err(err) => err.print()?,
                     },
+                    Some(&"free") => match free::Args::try_parse_from(&cmd) {
+                        Ok(args) => free::free(&mut state, args)?,
+                        Err(err) => err.print()?,
+                    },
+                    Some(&"inject") => match inject::Args::try_parse_from(&cmd) {
+                        Ok(args) => inject::inject(&mut state, args)?,
+                        Err(err) => err.print()?,
+                    },
+                    Some(&"modules") => match modules::Args::try_parse_from(&cmd) {
+                        Ok(args) => modules::modules(&mut state, args)?,
+                        Err(err) => err.print()?,
+                    },
+                    Some(&"freeze") => match freeze::Args::try_parse_from(&cmd) {
+                        Ok(args) => freeze::freeze(&mut state, args)?,
+                        Err(err) => err.print()?,
+                    },
+                    Some(&"hook") => match hook::Args::try_parse_from(&cmd) {
+                        Ok(args) => hook::hook(&mut state, args)?,
+                        Err(err) => err.print()?,
+                    },
+                    Some(&"patch") => match patch::Args::try_parse_from(&cmd) {
+                        Ok(args) => patch::patch(&mut state, args)?,
+                        Err(err) => err.print()?,
+                    },
+                    Some(&"pointer") => match pointer::Args::try_parse_from(&cmd) {
+                        Ok(args) => pointer::pointer(&mut state, args)?,
+                        Err(err) => err.print()?,
+                    },
+                    Some(&"protect") => match protect::Args::try_parse_from(&cmd) {
+                        Ok(args) => protect::protect(&mut state, args)?,
+                        Err(err) => err.print()?,
+                    },
+                    Some(&"regs") => match regs::Args::try_parse_from(&cmd) {
+                        Ok(args) => regs::regs(&mut state, args)?,
+                        Err(err) => err.print()?,
+                    },
                     Some(&"scan") => match scan::Args::try_parse_from(&cmd) {
                         Ok(args) => scan::scan(&mut state, args)?,
                         Err(err) => err.print()?,
                     },
+                    Some(&"symbol") => match symbol::Args::try_parse_from(&cmd) {
+                        Ok(args) => symbol::symbol(&mut state, args)?,
+                        Err(err) => err.print()?,
+                    },
+                    Some(&"threads") => match threads::Args::try_parse_from(&cmd) {
+                        Ok(args) => threads::threads(&mut state, args)?,
+                        Err(err) => err.print()?,
+                    },
+                    Some(&"watch") => match watch::Args::try_parse_from(&cmd) {
+                        Ok(args) => watch::watch(&mut state, args)?,
+                        Err(err) => err.print()?,
+                    },
                     Some(&"write") => match write::Args::try_parse_from(&cmd) {
                         Ok(args) => write::write(&mut state, args)?,
                         Err(err) => err.print()?,
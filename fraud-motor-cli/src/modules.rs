@@ -0,0 +1,42 @@
+use crate::state::State;
+use clap::Parser;
+use std::error::Error;
+
+#[derive(Parser)]
+pub struct Args {
+    name: Option<String>,
+}
+
+pub fn modules(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
+    for module in state.proc.modules()? {
+        let module = module?;
+
+        if let Some(name) = &args.name {
+            if module.is(name) {
+                for region in module.segments() {
+                    let perms = region.permissions();
+
+                    println!(
+                        "{:016x}-{:016x} {}{}{} {:08x}",
+                        region.start(),
+                        region.end(),
+                        if perms.read() { "r" } else { "-" },
+                        if perms.write() { "w" } else { "-" },
+                        if perms.exec() { "x" } else { "-" },
+                        region.offset(),
+                    );
+                }
+            }
+        } else {
+            println!(
+                "{:016x}-{:016x} {:8x} {}",
+                module.base(),
+                module.end(),
+                module.size(),
+                module.path().display(),
+            );
+        }
+    }
+
+    Ok(())
+}
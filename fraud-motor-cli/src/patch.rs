@@ -0,0 +1,145 @@
+use crate::state::State;
+use clap::{Parser, Subcommand};
+use std::error::Error;
+use std::str::{self, FromStr};
+
+#[derive(Parser)]
+pub struct Args {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    Add {
+        name: String,
+        addr: String,
+        bytes: Bytes,
+        #[arg(long)]
+        expect: Option<Bytes>,
+    },
+    Nop {
+        name: String,
+        addr: String,
+        len: usize,
+        #[arg(long)]
+        expect: Option<Bytes>,
+    },
+    Enable {
+        name: String,
+    },
+    Disable {
+        name: String,
+    },
+    Toggle {
+        name: String,
+    },
+    Del {
+        name: String,
+    },
+    List,
+}
+
+#[derive(Clone)]
+struct Bytes(Vec<u8>);
+
+pub fn patch(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
+    let result = match args.command {
+        Commands::Add {
+            name,
+            addr,
+            bytes,
+            expect,
+        } => {
+            let Some(addr) = state.try_resolve(&addr) else {
+                return Ok(());
+            };
+
+            state
+                .patches
+                .add(
+                    &name,
+                    addr,
+                    &bytes.0,
+                    expect.as_ref().map(|expect| &expect.0[..]),
+                )
+                .map_err(|err| (name, err))
+        }
+        Commands::Nop {
+            name,
+            addr,
+            len,
+            expect,
+        } => {
+            let Some(addr) = state.try_resolve(&addr) else {
+                return Ok(());
+            };
+
+            state
+                .patches
+                .add_nop(
+                    &name,
+                    addr,
+                    len,
+                    expect.as_ref().map(|expect| &expect.0[..]),
+                )
+                .map_err(|err| (name, err))
+        }
+        Commands::Enable { name } => state.patches.enable(&name).map_err(|err| (name, err)),
+        Commands::Disable { name } => state.patches.disable(&name).map_err(|err| (name, err)),
+        Commands::Toggle { name } => state
+            .patches
+            .toggle(&name)
+            .map(|enabled| {
+                println!("{}: {}", name, if enabled { "enabled" } else { "disabled" });
+            })
+            .map_err(|err| (name, err)),
+        Commands::Del { name } => state.patches.remove(&name).map_err(|err| (name, err)),
+        Commands::List => {
+            for (name, patch) in state.patches.iter() {
+                println!(
+                    "{} {:016x} {} {} -> {}{}",
+                    if patch.enabled() { "+" } else { "-" },
+                    patch.addr(),
+                    name,
+                    hex(patch.original()),
+                    hex(patch.bytes()),
+                    state.locate(patch.addr()),
+                );
+            }
+
+            Ok(())
+        }
+    };
+
+    if let Err((name, err)) = result {
+        println!("{}: {}", name, err);
+    }
+
+    Ok(())
+}
+
+impl FromStr for Bytes {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Bytes, String> {
+        if !s.len().is_multiple_of(2) {
+            return Err("odd number of hex digits".to_owned());
+        }
+
+        s.as_bytes()
+            .chunks(2)
+            .map(|digits| {
+                str::from_utf8(digits)
+                    .ok()
+                    .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+                    .ok_or_else(|| format!("invalid hex byte: {}", String::from_utf8_lossy(digits)))
+            })
+            .collect::<Result<_, _>>()
+            .map(Bytes)
+    }
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+}
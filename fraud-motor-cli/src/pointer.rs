@@ -0,0 +1,61 @@
+use crate::state::{self, State};
+use clap::Parser;
+use fraud_motor_core::dump::ProcessDump;
+use fraud_motor_core::pointer::{PointerMap, Root};
+use std::error::Error;
+
+#[derive(Parser)]
+pub struct Args {
+    addr: String,
+    /// Maximum number of pointers in a chain
+    #[arg(long, default_value_t = 4)]
+    depth: usize,
+    /// Maximum offset added to each pointer
+    #[arg(long, default_value = "0x1000", value_parser = state::parse_size)]
+    offset: usize,
+    /// Maximum number of chains to print
+    #[arg(long, default_value_t = 100)]
+    limit: usize,
+    /// Also use thread stacks as roots
+    #[arg(long)]
+    stacks: bool,
+    /// Use an existing dump instead of dumping writable memory
+    #[arg(long)]
+    dump: Option<String>,
+}
+
+pub fn pointer(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
+    let Some(addr) = state.try_resolve(&args.addr) else {
+        return Ok(());
+    };
+
+    let mut roots = Root::modules(&state.proc)?;
+
+    if args.stacks {
+        roots.extend(Root::stacks(&state.proc)?);
+    }
+
+    let map = match &args.dump {
+        Some(name) => match state.dumps.get(name) {
+            Some(dump) => PointerMap::new(dump, roots),
+            None => {
+                println!("{}: dump not found", name);
+
+                return Ok(());
+            }
+        },
+        None => {
+            let dump = ProcessDump::new(&state.memory, &state.proc, |region| {
+                region.permissions().write()
+            })?;
+
+            PointerMap::new(&dump, roots)
+        }
+    };
+
+    for chain in map.find(addr, args.depth, args.offset, args.limit) {
+        println!("{}", chain);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,30 @@
+use crate::state::{self, State};
+use clap::Parser;
+use fraud_motor_core::process::Permissions;
+use std::error::Error;
+
+#[derive(Parser)]
+pub struct Args {
+    addr: String,
+    #[arg(value_parser = state::parse_size)]
+    size: usize,
+    perms: Permissions,
+}
+
+pub fn protect(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
+    let Some(addr) = state.try_resolve(&args.addr) else {
+        return Ok(());
+    };
+
+    let Some(end) = addr.checked_add(args.size) else {
+        println!("{}: size out of range", args.addr);
+
+        return Ok(());
+    };
+
+    if let Err(err) = state.proc.protect(addr..end, &args.perms) {
+        println!("{}: {}", args.addr, err);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use crate::state::State;
+use clap::Parser;
+use fraud_motor_core::thread::Registers;
+use std::error::Error;
+
+#[derive(Parser)]
+pub struct Args {
+    tid: u32,
+    /// Registers to set, as <reg>=<value>
+    set: Vec<String>,
+}
+
+pub fn regs(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
+    let threads = state
+        .proc
+        .threads()
+        .and_then(|threads| threads.collect::<Result<Vec<_>, _>>());
+    let threads = match threads {
+        Ok(threads) => threads,
+        Err(err) => {
+            println!("{}: {}", args.tid, err);
+
+            return Ok(());
+        }
+    };
+    let thread = threads.into_iter().find(|thread| thread.id() == args.tid);
+
+    let Some(thread) = thread else {
+        println!("{}: thread not found", args.tid);
+
+        return Ok(());
+    };
+
+    let mut registers = match thread.registers() {
+        Ok(registers) => registers,
+        Err(err) => {
+            println!("{}: {}", args.tid, err);
+
+            return Ok(());
+        }
+    };
+
+    if !args.set.is_empty() {
+        for set in &args.set {
+            let value = set.split_once('=').and_then(|(name, value)| {
+                let value = match value.strip_prefix("0x") {
+                    Some(hex) => u64::from_str_radix(hex, 16).ok()?,
+                    None => value.parse().ok()?,
+                };
+
+                Some((registers.get_mut(name)?, value))
+            });
+
+            match value {
+                Some((reg, value)) => *reg = value,
+                None => {
+                    println!("{}: invalid register assignment", set);
+
+                    return Ok(());
+                }
+            }
+        }
+
+        if let Err(err) = thread.set_registers(&registers) {
+            println!("{}: {}", args.tid, err);
+
+            return Ok(());
+        }
+    }
+
+    for name in Registers::NAMES {
+        let value = registers.get(name).unwrap();
+
+        println!(
+            "{:>7} {:016x}{}",
+            name,
+            value,
+            state.describe(value as usize)
+        );
+    }
+
+    Ok(())
+}
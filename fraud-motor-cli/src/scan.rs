@@ -1,9 +1,8 @@
 use crate::state::{ScanGroup, State};
 use clap::{Parser, Subcommand};
 use fraud_motor_core::dump::{DumpView, ProcessDump};
-use fraud_motor_core::memory::Memory;
 use fraud_motor_core::process::Region;
-use fraud_motor_core::scan::Scan;
+use fraud_motor_core::scan::{Encoding, FloatMode, Pattern, Scan, TextPattern};
 use std::error::Error;
 use std::fmt::Display;
 use std::mem;
@@ -23,6 +22,12 @@ enum Commands {
         types: Types,
         #[arg(long)]
         align: Option<usize>,
+        /// Read values as big-endian
+        #[arg(long)]
+        be: bool,
+        /// Remember the current values so later scans can use relative filters
+        #[arg(long)]
+        unknown: bool,
     },
     Drop {
         name: String,
@@ -33,8 +38,39 @@ enum Commands {
     Next {
         name: String,
         dump: Option<String>,
+        /// Compare relative filters against this dump instead of the previous values
+        #[arg(long)]
+        prev: Option<String>,
         #[command(flatten)]
-        filters: Filters,
+        filters: Box<Filters>,
+    },
+    /// Search for an array of bytes, e.g. "48 8B 05 ?? ?? ?? ?? 4? 89"
+    Aob {
+        #[arg(required = true)]
+        pattern: Vec<String>,
+        /// Only search the executable segments of this module
+        #[arg(long, conflicts_with = "dump")]
+        module: Option<String>,
+        #[arg(long)]
+        dump: Option<String>,
+    },
+    /// Search for text in one or more encodings
+    String {
+        #[arg(required = true)]
+        text: Vec<String>,
+        /// utf8, utf16le, utf16be or latin1 (default: all)
+        #[arg(long)]
+        encoding: Vec<Encoding>,
+        #[arg(short, long)]
+        ignore_case: bool,
+        /// Require the text to be followed by a null terminator
+        #[arg(long)]
+        null: bool,
+        /// Number of characters of context to print around each match
+        #[arg(long, default_value_t = 16)]
+        context: usize,
+        #[arg(long)]
+        dump: Option<String>,
     },
 }
 
@@ -48,6 +84,9 @@ struct Types {
     u32: bool,
     #[arg(long)]
     u64: bool,
+    /// Not scanned unless requested
+    #[arg(long)]
+    u128: bool,
     #[arg(long)]
     i8: bool,
     #[arg(long)]
@@ -56,6 +95,9 @@ struct Types {
     i32: bool,
     #[arg(long)]
     i64: bool,
+    /// Not scanned unless requested
+    #[arg(long)]
+    i128: bool,
     #[arg(long)]
     f32: bool,
     #[arg(long)]
@@ -76,6 +118,136 @@ struct Filters {
     lt: Vec<String>,
     #[arg(long)]
     le: Vec<String>,
+    #[arg(long)]
+    changed: bool,
+    #[arg(long)]
+    unchanged: bool,
+    #[arg(long)]
+    increased: bool,
+    #[arg(long)]
+    decreased: bool,
+    #[arg(long)]
+    increased_by: Vec<String>,
+    #[arg(long)]
+    decreased_by: Vec<String>,
+    /// Keep values that changed by at least this many percent (never from zero)
+    #[arg(long)]
+    changed_by_percent: Vec<f64>,
+    /// Keep values between two bounds (inclusive)
+    #[arg(long, num_args = 2, value_names = ["MIN", "MAX"])]
+    between: Vec<String>,
+    /// Compare floats with an absolute tolerance
+    #[arg(long, conflicts_with_all = ["relative", "round", "truncate"])]
+    epsilon: Option<f64>,
+    /// Compare floats with a tolerance relative to their magnitude
+    #[arg(long, conflicts_with_all = ["round", "truncate"])]
+    relative: Option<f64>,
+    /// Compare floats after rounding them to this many decimals
+    #[arg(long, conflicts_with = "truncate")]
+    round: Option<u32>,
+    /// Compare floats after truncating them to this many decimals
+    #[arg(long)]
+    truncate: Option<u32>,
+}
+
+trait Number: Copy + PartialOrd + FromStr {
+    fn eq_mode(self, rhs: Self, mode: FloatMode) -> bool;
+    fn add(self, rhs: Self) -> Option<Self>;
+    fn sub(self, rhs: Self) -> Option<Self>;
+    fn to_f64(self) -> f64;
+}
+
+macro_rules! number_int {
+    ($($ty:ty),*) => {
+        $(impl Number for $ty {
+            fn eq_mode(self, rhs: $ty, _: FloatMode) -> bool {
+                self == rhs
+            }
+
+            fn add(self, rhs: $ty) -> Option<$ty> {
+                self.checked_add(rhs)
+            }
+
+            fn sub(self, rhs: $ty) -> Option<$ty> {
+                self.checked_sub(rhs)
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        })*
+    };
+}
+
+macro_rules! number_float {
+    ($($ty:ty),*) => {
+        $(impl Number for $ty {
+            fn eq_mode(self, rhs: $ty, mode: FloatMode) -> bool {
+                mode.eq(self as f64, rhs as f64)
+            }
+
+            fn add(self, rhs: $ty) -> Option<$ty> {
+                Some(self + rhs)
+            }
+
+            fn sub(self, rhs: $ty) -> Option<$ty> {
+                Some(self - rhs)
+            }
+
+            fn to_f64(self) -> f64 {
+                self as f64
+            }
+        })*
+    };
+}
+
+macro_rules! cvt {
+    ($ty:ty, $be:expr) => {
+        move |bytes| {
+            if $be {
+                <$ty>::from_be_bytes(bytes)
+            } else {
+                <$ty>::from_ne_bytes(bytes)
+            }
+        }
+    };
+}
+
+number_int!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+number_float!(f32, f64);
+
+impl Filters {
+    fn float_mode(&self) -> FloatMode {
+        if let Some(epsilon) = self.epsilon {
+            FloatMode::Absolute(epsilon)
+        } else if let Some(epsilon) = self.relative {
+            FloatMode::Relative(epsilon)
+        } else if let Some(decimals) = self.round {
+            FloatMode::Rounded(decimals)
+        } else if let Some(decimals) = self.truncate {
+            FloatMode::Truncated(decimals)
+        } else {
+            FloatMode::Exact
+        }
+    }
+
+    fn relative(&self) -> bool {
+        self.changed
+            || self.unchanged
+            || self.increased
+            || self.decreased
+            || !self.increased_by.is_empty()
+            || !self.decreased_by.is_empty()
+            || !self.changed_by_percent.is_empty()
+    }
+}
+
+fn ty_name(ty: &str, be: bool) -> String {
+    if be {
+        format!("{}be", ty)
+    } else {
+        ty.to_owned()
+    }
 }
 
 fn scan_new(flag: bool, align: usize) -> Option<Scan> {
@@ -92,21 +264,28 @@ fn scan_insert(scan: Option<&mut Scan>, region: &Region) {
     }
 }
 
-fn scan_info<T, Cvt, const N: usize>(scan: Option<&Scan>, memory: &Memory, ty: &str, mut cvt: Cvt)
+fn scan_info<T, Cvt, const N: usize>(scan: Option<&Scan>, state: &State, ty: &str, mut cvt: Cvt)
 where
     T: Display,
     Cvt: FnMut([u8; N]) -> T,
 {
     if let Some(scan) = scan {
-        for addr in scan.iter() {
-            print!("{}:{}", ty, addr);
+        let addrs: Vec<_> = scan.iter().collect();
+        let mut bufs = vec![[0; N]; addrs.len()];
+        let mut reads: Vec<_> = addrs
+            .iter()
+            .copied()
+            .zip(bufs.iter_mut().map(|buf| &mut buf[..]))
+            .collect();
+        let results = state.memory.read_many(&mut reads);
 
-            let mut buf = [0; N];
+        for ((addr, buf), result) in addrs.into_iter().zip(bufs).zip(results) {
+            print!("{}:{}", ty, addr);
 
-            if memory.read(&mut buf, addr).is_ok() {
-                println!(" {}", cvt(buf));
+            if result.is_ok() {
+                println!(" {}{}", cvt(buf), state.describe(addr));
             } else {
-                println!();
+                println!("{}", state.describe(addr));
             }
         }
     }
@@ -149,31 +328,156 @@ fn scan_next<T, Cvt, const N: usize>(
     filters: &Filters,
     cvt: Cvt,
 ) where
-    T: PartialEq + PartialOrd + FromStr + Copy,
+    T: Number,
     T::Err: Error + 'static,
     Cvt: FnMut([u8; N]) -> T + Copy,
 {
+    let mode = filters.float_mode();
+
     if let Some(scan) = scan {
-        scan_next_imp(scan, view, &filters.eq, cvt, |a, b| a == b);
-        scan_next_imp(scan, view, &filters.ne, cvt, |a, b| a != b);
+        scan_next_imp(scan, view, &filters.eq, cvt, |a, b| a.eq_mode(b, mode));
+        scan_next_imp(scan, view, &filters.ne, cvt, |a, b| !a.eq_mode(b, mode));
         scan_next_imp(scan, view, &filters.gt, cvt, |a, b| a > b);
         scan_next_imp(scan, view, &filters.ge, cvt, |a, b| a >= b);
         scan_next_imp(scan, view, &filters.lt, cvt, |a, b| a < b);
         scan_next_imp(scan, view, &filters.le, cvt, |a, b| a <= b);
+
+        for bounds in filters.between.chunks(2) {
+            scan_next_imp(scan, view, &bounds[..1], cvt, |a, b| a >= b);
+            scan_next_imp(scan, view, &bounds[1..], cvt, |a, b| a <= b);
+        }
+    }
+}
+
+fn printable(encoding: Encoding, bytes: &[u8]) -> String {
+    encoding
+        .decode_lossy(bytes)
+        .chars()
+        .map(|c| if c.is_control() { '.' } else { c })
+        .collect()
+}
+
+fn scan_changes_imp<T, Cvt, Cmp, const N: usize>(
+    scan: &mut Scan,
+    previous: Option<&ProcessDump>,
+    current: &ProcessDump,
+    cvt: Cvt,
+    mut cmp: Cmp,
+) where
+    Cvt: Fn([u8; N]) -> T,
+    Cmp: FnMut(T, T) -> bool,
+{
+    let filter = |a: &[u8], b: &[u8]| cmp(cvt(a.try_into().unwrap()), cvt(b.try_into().unwrap()));
+
+    match previous {
+        Some(previous) => scan.retain_changes(previous, current, N, filter),
+        None => scan.retain_previous(current, filter),
+    }
+}
+
+fn scan_remember(scan: Option<&mut Scan>, current: &ProcessDump, size: usize) {
+    if let Some(scan) = scan {
+        scan.remember(current, size);
+    }
+}
+
+fn scan_remember_all(scan: &mut ScanGroup, current: &ProcessDump) {
+    scan_remember(scan.u8.as_mut(), current, 1);
+    scan_remember(scan.u16.as_mut(), current, 2);
+    scan_remember(scan.u32.as_mut(), current, 4);
+    scan_remember(scan.u64.as_mut(), current, 8);
+    scan_remember(scan.u128.as_mut(), current, 16);
+    scan_remember(scan.i8.as_mut(), current, 1);
+    scan_remember(scan.i16.as_mut(), current, 2);
+    scan_remember(scan.i32.as_mut(), current, 4);
+    scan_remember(scan.i64.as_mut(), current, 8);
+    scan_remember(scan.i128.as_mut(), current, 16);
+    scan_remember(scan.f32.as_mut(), current, 4);
+    scan_remember(scan.f64.as_mut(), current, 8);
+}
+
+fn scan_changes<T, Cvt, const N: usize>(
+    scan: Option<&mut Scan>,
+    previous: Option<&ProcessDump>,
+    current: &ProcessDump,
+    filters: &Filters,
+    cvt: Cvt,
+) where
+    T: Number,
+    Cvt: Fn([u8; N]) -> T + Copy,
+{
+    let Some(scan) = scan else {
+        return;
+    };
+
+    let mode = filters.float_mode();
+
+    if filters.changed {
+        scan_changes_imp(scan, previous, current, cvt, |a, b| {
+            !b.eq_mode(a, FloatMode::Exact)
+        });
+    }
+
+    if filters.unchanged {
+        scan_changes_imp(scan, previous, current, cvt, |a, b| {
+            b.eq_mode(a, FloatMode::Exact)
+        });
+    }
+
+    if filters.increased {
+        scan_changes_imp(scan, previous, current, cvt, |a, b| b > a);
+    }
+
+    if filters.decreased {
+        scan_changes_imp(scan, previous, current, cvt, |a, b| b < a);
+    }
+
+    for expr in &filters.increased_by {
+        match expr.parse::<T>() {
+            Ok(n) => scan_changes_imp(scan, previous, current, cvt, |a, b| {
+                a.add(n).is_some_and(|a| b.eq_mode(a, mode))
+            }),
+            Err(_) => scan.retain(|_| false),
+        }
+    }
+
+    for expr in &filters.decreased_by {
+        match expr.parse::<T>() {
+            Ok(n) => scan_changes_imp(scan, previous, current, cvt, |a, b| {
+                a.sub(n).is_some_and(|a| b.eq_mode(a, mode))
+            }),
+            Err(_) => scan.retain(|_| false),
+        }
+    }
+
+    for &percent in &filters.changed_by_percent {
+        scan_changes_imp(scan, previous, current, cvt, |a: T, b: T| {
+            let (a, b) = (a.to_f64(), b.to_f64());
+
+            a != 0.0 && a != b && (b - a).abs() >= a.abs() * percent / 100.0
+        });
     }
 }
 
 pub fn scan(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
     match args.command {
-        Commands::New { name, types, align } => {
+        Commands::New {
+            name,
+            types,
+            align,
+            be,
+            unknown,
+        } => {
             let all = !types.u8
                 && !types.u16
                 && !types.u32
                 && !types.u64
+                && !types.u128
                 && !types.i8
                 && !types.i16
                 && !types.i32
                 && !types.i64
+                && !types.i128
                 && !types.f32
                 && !types.f64;
 
@@ -182,12 +486,16 @@ pub fn scan(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
                 u16: scan_new(types.u16 || all, align.unwrap_or(2)),
                 u32: scan_new(types.u32 || all, align.unwrap_or(4)),
                 u64: scan_new(types.u64 || all, align.unwrap_or(8)),
+                u128: scan_new(types.u128, align.unwrap_or(16)),
                 i8: scan_new(types.i8 || all, align.unwrap_or(1)),
                 i16: scan_new(types.i16 || all, align.unwrap_or(2)),
                 i32: scan_new(types.i32 || all, align.unwrap_or(4)),
                 i64: scan_new(types.i64 || all, align.unwrap_or(8)),
+                i128: scan_new(types.i128, align.unwrap_or(16)),
                 f32: scan_new(types.f32 || all, align.unwrap_or(4)),
                 f64: scan_new(types.f64 || all, align.unwrap_or(8)),
+                big_endian: be,
+                remember: unknown,
             };
 
             for region in state.proc.regions()? {
@@ -198,15 +506,25 @@ pub fn scan(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
                     scan_insert(scan.u16.as_mut(), &region);
                     scan_insert(scan.u32.as_mut(), &region);
                     scan_insert(scan.u64.as_mut(), &region);
+                    scan_insert(scan.u128.as_mut(), &region);
                     scan_insert(scan.i8.as_mut(), &region);
                     scan_insert(scan.i16.as_mut(), &region);
                     scan_insert(scan.i32.as_mut(), &region);
                     scan_insert(scan.i64.as_mut(), &region);
+                    scan_insert(scan.i128.as_mut(), &region);
                     scan_insert(scan.f32.as_mut(), &region);
                     scan_insert(scan.f64.as_mut(), &region);
                 }
             }
 
+            if unknown {
+                let current = ProcessDump::new(&state.memory, &state.proc, |region| {
+                    region.permissions().write()
+                })?;
+
+                scan_remember_all(&mut scan, &current);
+            }
+
             state.scans.insert(name, scan);
         }
         Commands::Drop { name } => {
@@ -215,72 +533,242 @@ pub fn scan(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
         Commands::Info { name } => {
             if let Some(name) = name {
                 if let Some(scan) = state.scans.get(&name) {
-                    scan_info(scan.u8.as_ref(), &state.memory, "u8", u8::from_ne_bytes);
-                    scan_info(scan.u16.as_ref(), &state.memory, "u16", u16::from_ne_bytes);
-                    scan_info(scan.u32.as_ref(), &state.memory, "u32", u32::from_ne_bytes);
-                    scan_info(scan.u64.as_ref(), &state.memory, "u64", u64::from_ne_bytes);
-                    scan_info(scan.i8.as_ref(), &state.memory, "i8", i8::from_ne_bytes);
-                    scan_info(scan.i16.as_ref(), &state.memory, "i16", i16::from_ne_bytes);
-                    scan_info(scan.i32.as_ref(), &state.memory, "i32", i32::from_ne_bytes);
-                    scan_info(scan.i64.as_ref(), &state.memory, "i64", i64::from_ne_bytes);
-                    scan_info(scan.f32.as_ref(), &state.memory, "f32", f32::from_ne_bytes);
-                    scan_info(scan.f64.as_ref(), &state.memory, "f64", f64::from_ne_bytes);
+                    let be = scan.big_endian;
+
+                    scan_info(scan.u8.as_ref(), state, &ty_name("u8", be), cvt!(u8, be));
+                    scan_info(scan.u16.as_ref(), state, &ty_name("u16", be), cvt!(u16, be));
+                    scan_info(scan.u32.as_ref(), state, &ty_name("u32", be), cvt!(u32, be));
+                    scan_info(scan.u64.as_ref(), state, &ty_name("u64", be), cvt!(u64, be));
+                    scan_info(
+                        scan.u128.as_ref(),
+                        state,
+                        &ty_name("u128", be),
+                        cvt!(u128, be),
+                    );
+                    scan_info(scan.i8.as_ref(), state, &ty_name("i8", be), cvt!(i8, be));
+                    scan_info(scan.i16.as_ref(), state, &ty_name("i16", be), cvt!(i16, be));
+                    scan_info(scan.i32.as_ref(), state, &ty_name("i32", be), cvt!(i32, be));
+                    scan_info(scan.i64.as_ref(), state, &ty_name("i64", be), cvt!(i64, be));
+                    scan_info(
+                        scan.i128.as_ref(),
+                        state,
+                        &ty_name("i128", be),
+                        cvt!(i128, be),
+                    );
+                    scan_info(scan.f32.as_ref(), state, &ty_name("f32", be), cvt!(f32, be));
+                    scan_info(scan.f64.as_ref(), state, &ty_name("f64", be), cvt!(f64, be));
                 } else {
                     println!("{}: scan not found", name);
                 }
             } else {
                 for (name, scan) in &state.scans {
-                    scan_info_name(scan.u8.as_ref(), name, "u8");
-                    scan_info_name(scan.u16.as_ref(), name, "u16");
-                    scan_info_name(scan.u32.as_ref(), name, "u32");
-                    scan_info_name(scan.u64.as_ref(), name, "u64");
-                    scan_info_name(scan.i8.as_ref(), name, "i8");
-                    scan_info_name(scan.i16.as_ref(), name, "i16");
-                    scan_info_name(scan.i32.as_ref(), name, "i32");
-                    scan_info_name(scan.i64.as_ref(), name, "i64");
-                    scan_info_name(scan.f32.as_ref(), name, "f32");
-                    scan_info_name(scan.f64.as_ref(), name, "f64");
+                    let be = scan.big_endian;
+
+                    scan_info_name(scan.u8.as_ref(), name, &ty_name("u8", be));
+                    scan_info_name(scan.u16.as_ref(), name, &ty_name("u16", be));
+                    scan_info_name(scan.u32.as_ref(), name, &ty_name("u32", be));
+                    scan_info_name(scan.u64.as_ref(), name, &ty_name("u64", be));
+                    scan_info_name(scan.u128.as_ref(), name, &ty_name("u128", be));
+                    scan_info_name(scan.i8.as_ref(), name, &ty_name("i8", be));
+                    scan_info_name(scan.i16.as_ref(), name, &ty_name("i16", be));
+                    scan_info_name(scan.i32.as_ref(), name, &ty_name("i32", be));
+                    scan_info_name(scan.i64.as_ref(), name, &ty_name("i64", be));
+                    scan_info_name(scan.i128.as_ref(), name, &ty_name("i128", be));
+                    scan_info_name(scan.f32.as_ref(), name, &ty_name("f32", be));
+                    scan_info_name(scan.f64.as_ref(), name, &ty_name("f64", be));
                 }
             }
         }
         Commands::Next {
             name,
             dump,
+            prev,
             filters,
         } => {
             if let Some(scan) = state.scans.get_mut(&name) {
-                let tmp_dump;
+                let mut tmp_dump = None;
 
-                let mut view = if let Some(dump) = dump {
-                    if let Some(dump) = state.dumps.get(&dump) {
-                        dump.view()
+                let current = if let Some(dump) = &dump {
+                    if let Some(dump) = state.dumps.get(dump) {
+                        dump
                     } else {
                         println!("{}: dump not found", dump);
 
                         return Ok(());
                     }
                 } else {
-                    tmp_dump = ProcessDump::new(&state.memory, &state.proc, |region| {
+                    tmp_dump.insert(ProcessDump::new(&state.memory, &state.proc, |region| {
                         region.permissions().write()
-                    })?;
+                    })?)
+                };
 
-                    tmp_dump.view()
+                let previous = match &prev {
+                    Some(prev) => match state.dumps.get(prev) {
+                        Some(dump) => Some(dump),
+                        None => {
+                            println!("{}: dump not found", prev);
+
+                            return Ok(());
+                        }
+                    },
+                    None => None,
                 };
 
-                scan_next(scan.u8.as_mut(), &mut view, &filters, u8::from_ne_bytes);
-                scan_next(scan.u16.as_mut(), &mut view, &filters, u16::from_ne_bytes);
-                scan_next(scan.u32.as_mut(), &mut view, &filters, u32::from_ne_bytes);
-                scan_next(scan.u64.as_mut(), &mut view, &filters, u64::from_ne_bytes);
-                scan_next(scan.i8.as_mut(), &mut view, &filters, i8::from_ne_bytes);
-                scan_next(scan.i16.as_mut(), &mut view, &filters, i16::from_ne_bytes);
-                scan_next(scan.i32.as_mut(), &mut view, &filters, i32::from_ne_bytes);
-                scan_next(scan.i64.as_mut(), &mut view, &filters, i64::from_ne_bytes);
-                scan_next(scan.f32.as_mut(), &mut view, &filters, f32::from_ne_bytes);
-                scan_next(scan.f64.as_mut(), &mut view, &filters, f64::from_ne_bytes);
+                if filters.relative() && previous.is_none() && !scan.remember {
+                    println!(
+                        "{}: no previous values, use `scan new --unknown` or --prev",
+                        name
+                    );
+
+                    return Ok(());
+                }
+
+                let mut view = current.view();
+
+                let be = scan.big_endian;
+
+                scan_next(scan.u8.as_mut(), &mut view, &filters, cvt!(u8, be));
+                scan_next(scan.u16.as_mut(), &mut view, &filters, cvt!(u16, be));
+                scan_next(scan.u32.as_mut(), &mut view, &filters, cvt!(u32, be));
+                scan_next(scan.u64.as_mut(), &mut view, &filters, cvt!(u64, be));
+                scan_next(scan.u128.as_mut(), &mut view, &filters, cvt!(u128, be));
+                scan_next(scan.i8.as_mut(), &mut view, &filters, cvt!(i8, be));
+                scan_next(scan.i16.as_mut(), &mut view, &filters, cvt!(i16, be));
+                scan_next(scan.i32.as_mut(), &mut view, &filters, cvt!(i32, be));
+                scan_next(scan.i64.as_mut(), &mut view, &filters, cvt!(i64, be));
+                scan_next(scan.i128.as_mut(), &mut view, &filters, cvt!(i128, be));
+                scan_next(scan.f32.as_mut(), &mut view, &filters, cvt!(f32, be));
+                scan_next(scan.f64.as_mut(), &mut view, &filters, cvt!(f64, be));
+
+                if filters.relative() {
+                    let cur = current;
+
+                    scan_changes(scan.u8.as_mut(), previous, cur, &filters, cvt!(u8, be));
+                    scan_changes(scan.u16.as_mut(), previous, cur, &filters, cvt!(u16, be));
+                    scan_changes(scan.u32.as_mut(), previous, cur, &filters, cvt!(u32, be));
+                    scan_changes(scan.u64.as_mut(), previous, cur, &filters, cvt!(u64, be));
+                    scan_changes(scan.u128.as_mut(), previous, cur, &filters, cvt!(u128, be));
+                    scan_changes(scan.i8.as_mut(), previous, cur, &filters, cvt!(i8, be));
+                    scan_changes(scan.i16.as_mut(), previous, cur, &filters, cvt!(i16, be));
+                    scan_changes(scan.i32.as_mut(), previous, cur, &filters, cvt!(i32, be));
+                    scan_changes(scan.i64.as_mut(), previous, cur, &filters, cvt!(i64, be));
+                    scan_changes(scan.i128.as_mut(), previous, cur, &filters, cvt!(i128, be));
+                    scan_changes(scan.f32.as_mut(), previous, cur, &filters, cvt!(f32, be));
+                    scan_changes(scan.f64.as_mut(), previous, cur, &filters, cvt!(f64, be));
+                }
+
+                if scan.remember {
+                    scan_remember_all(scan, current);
+                }
             } else {
                 println!("{}: scan not found", name);
             }
         }
+        Commands::Aob {
+            pattern,
+            module,
+            dump,
+        } => {
+            let pattern = pattern.join(" ");
+            let pattern = pattern.trim_matches('"');
+            let pattern: Pattern = match pattern.parse() {
+                Ok(pattern) => pattern,
+                Err(err) => {
+                    println!("{}: {}", pattern, err);
+
+                    return Ok(());
+                }
+            };
+
+            let matches = if let Some(name) = module {
+                let modules: Vec<_> = state.proc.modules()?.collect::<Result<_, _>>()?;
+
+                if let Some(module) = modules.iter().find(|module| module.is(&name)) {
+                    pattern.scan_module(&state.memory, module)?
+                } else {
+                    println!("{}: module not found", name);
+
+                    return Ok(());
+                }
+            } else if let Some(name) = dump {
+                if let Some(dump) = state.dumps.get(&name) {
+                    pattern.scan_dump(dump)
+                } else {
+                    println!("{}: dump not found", name);
+
+                    return Ok(());
+                }
+            } else {
+                pattern.scan_memory(&state.memory, &state.proc, |region| {
+                    region.permissions().read()
+                })?
+            };
+
+            for addr in matches {
+                println!("{:016x}{}", addr, state.locate(addr));
+            }
+        }
+        Commands::String {
+            text,
+            encoding,
+            ignore_case,
+            null,
+            context,
+            dump,
+        } => {
+            let text = text.join(" ");
+            let text = text.trim_matches('"');
+            let encodings = if encoding.is_empty() {
+                Encoding::ALL.to_vec()
+            } else {
+                encoding
+            };
+            let tmp_dump;
+
+            let dump = if let Some(name) = dump {
+                if let Some(dump) = state.dumps.get(&name) {
+                    dump
+                } else {
+                    println!("{}: dump not found", name);
+
+                    return Ok(());
+                }
+            } else {
+                tmp_dump = ProcessDump::new(&state.memory, &state.proc, |region| {
+                    region.permissions().write()
+                })?;
+
+                &tmp_dump
+            };
+
+            for encoding in encodings {
+                let Some(pattern) = TextPattern::new(text, encoding, ignore_case, null) else {
+                    continue;
+                };
+
+                let mut view = dump.view();
+
+                for addr in pattern.scan_dump(dump) {
+                    let size = context * encoding.unit_size();
+                    let start = addr.saturating_sub(size);
+                    let mut read = |start: usize, len: usize| {
+                        view.data(start)
+                            .filter(|data| data.len() >= len)
+                            .map_or_else(String::new, |data| printable(encoding, &data[..len]))
+                    };
+
+                    println!(
+                        "{:016x} {:<7} {}[{}]{}{}",
+                        addr,
+                        encoding,
+                        read(start, addr - start),
+                        read(addr, pattern.len()),
+                        read(addr + pattern.len(), size),
+                        state.locate(addr),
+                    );
+                }
+            }
+        }
     };
 
     Ok(())
@@ -1,15 +1,29 @@
+use fraud_motor_core::debug::Debugger;
 use fraud_motor_core::dump::ProcessDump;
-use fraud_motor_core::memory::Memory;
-use fraud_motor_core::process::Process;
+use fraud_motor_core::freeze::Freezer;
+use fraud_motor_core::hook::Hook;
+use fraud_motor_core::memory::{Backend, Memory};
+use fraud_motor_core::patch::Patches;
+use fraud_motor_core::process::{Library, ModuleAddress, Process};
 use fraud_motor_core::scan::Scan;
-use std::collections::HashMap;
+use fraud_motor_core::symbol::Symbols;
+use std::collections::{BTreeMap, HashMap};
 use std::error::Error;
+use std::mem;
+use std::num::ParseIntError;
+use std::time::Duration;
 
 pub struct State {
     pub memory: Memory,
     pub proc: Process,
     pub dumps: HashMap<String, ProcessDump>,
     pub scans: HashMap<String, ScanGroup>,
+    pub symbols: Symbols,
+    pub debugger: Option<Debugger>,
+    pub libraries: Vec<Library>,
+    pub patches: Patches,
+    pub hooks: BTreeMap<String, Hook>,
+    pub freezer: Freezer,
 }
 
 pub struct ScanGroup {
@@ -17,21 +31,114 @@ pub struct ScanGroup {
     pub u16: Option<Scan>,
     pub u32: Option<Scan>,
     pub u64: Option<Scan>,
+    pub u128: Option<Scan>,
     pub i8: Option<Scan>,
     pub i16: Option<Scan>,
     pub i32: Option<Scan>,
     pub i64: Option<Scan>,
+    pub i128: Option<Scan>,
     pub f32: Option<Scan>,
     pub f64: Option<Scan>,
+    pub big_endian: bool,
+    pub remember: bool,
 }
 
 impl State {
-    pub fn new(pid: u32) -> Result<State, Box<dyn Error>> {
+    pub fn new(pid: u32, backend: Backend) -> Result<State, Box<dyn Error>> {
+        let proc = Process::open(pid)?;
+        let memory = || {
+            Memory::options()
+                .read(true)
+                .write(true)
+                .backend(backend)
+                .open(pid)
+        };
+
         Ok(State {
-            memory: Memory::options().read(true).write(true).open(pid)?,
-            proc: Process::open(pid)?,
+            memory: memory()?,
+            patches: Patches::new(memory()?, Process::open(pid)?),
+            freezer: Freezer::new(memory()?, Duration::from_millis(100))?,
+            symbols: Symbols::load(&proc)?,
+            proc,
             dumps: HashMap::new(),
             scans: HashMap::new(),
+            debugger: None,
+            libraries: Vec::new(),
+            hooks: BTreeMap::new(),
         })
     }
+
+    pub fn debugger(&mut self) -> Result<&Debugger, Box<dyn Error>> {
+        if self.debugger.is_none() {
+            self.debugger = Some(Debugger::attach(&self.proc)?);
+        }
+
+        Ok(self.debugger.as_ref().unwrap())
+    }
+
+    pub fn resolve(&self, addr: &str) -> Result<usize, Box<dyn Error>> {
+        if let Some(hex) = addr.strip_prefix("0x") {
+            return Ok(usize::from_str_radix(hex, 16)?);
+        }
+
+        if let Ok(addr) = addr.parse() {
+            return Ok(addr);
+        }
+
+        let addr: ModuleAddress = addr.parse()?;
+        let modules: Vec<_> = self.proc.modules()?.collect::<Result<_, _>>()?;
+
+        if let Some(addr) = addr.to_addr(&modules) {
+            Ok(addr)
+        } else if let Some(symbol) = self.symbols.find(addr.module()) {
+            Ok(symbol.addr() + addr.offset())
+        } else {
+            Err("module or symbol not found".into())
+        }
+    }
+
+    pub fn try_resolve(&self, addr: &str) -> Option<usize> {
+        match self.resolve(addr) {
+            Ok(value) => Some(value),
+            Err(err) => {
+                println!("{}: {}", addr, err);
+
+                None
+            }
+        }
+    }
+
+    pub fn locate(&self, addr: usize) -> String {
+        let modules: Vec<_> = match self.proc.modules() {
+            Ok(modules) => modules.filter_map(Result::ok).collect(),
+            Err(_) => Vec::new(),
+        };
+
+        match ModuleAddress::from_addr(&modules, addr) {
+            Some(module_addr) => format!(" {}{}", module_addr, self.describe(addr)),
+            None => self.describe(addr),
+        }
+    }
+
+    pub fn describe(&self, addr: usize) -> String {
+        match self.symbols.lookup(addr) {
+            Some(symbol) => format!(" {}", symbol),
+            None => String::new(),
+        }
+    }
+}
+
+impl Drop for State {
+    fn drop(&mut self) {
+        for (_, hook) in mem::take(&mut self.hooks) {
+            let _ = hook.uninstall(&self.proc);
+        }
+    }
+}
+
+pub fn parse_size(s: &str) -> Result<usize, ParseIntError> {
+    match s.strip_prefix("0x") {
+        Some(hex) => usize::from_str_radix(hex, 16),
+        None => s.parse(),
+    }
 }
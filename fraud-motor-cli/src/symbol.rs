@@ -0,0 +1,44 @@
+use crate::state::State;
+use clap::{Parser, Subcommand};
+use fraud_motor_core::symbol::Symbols;
+use std::error::Error;
+
+#[derive(Parser)]
+pub struct Args {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    Find { name: String },
+    Lookup { addr: String },
+    Reload,
+}
+
+pub fn symbol(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
+    match args.command {
+        Commands::Find { name } => {
+            if let Some(symbol) = state.symbols.find(&name) {
+                println!(
+                    "{:016x} {:8x} {} {}",
+                    symbol.addr(),
+                    symbol.size(),
+                    symbol.module(),
+                    symbol.name(),
+                );
+            } else {
+                println!("{}: symbol not found", name);
+            }
+        }
+        Commands::Lookup { addr } => match state.resolve(&addr) {
+            Ok(addr) => println!("{:016x}{}", addr, state.describe(addr)),
+            Err(err) => println!("{}: {}", addr, err),
+        },
+        Commands::Reload => {
+            state.symbols = Symbols::load(&state.proc)?;
+        }
+    };
+
+    Ok(())
+}
@@ -0,0 +1,43 @@
+use crate::state::State;
+use clap::Parser;
+use std::error::Error;
+
+#[derive(Parser)]
+pub struct Args {}
+
+pub fn threads(state: &mut State, _args: Args) -> Result<(), Box<dyn Error>> {
+    let threads = match state.proc.threads() {
+        Ok(threads) => threads,
+        Err(err) => {
+            println!("threads: {}", err);
+
+            return Ok(());
+        }
+    };
+
+    for thread in threads {
+        let thread = match thread {
+            Ok(thread) => thread,
+            Err(err) => {
+                println!("threads: {}", err);
+
+                continue;
+            }
+        };
+
+        print!(
+            "{:>8} {:?} {:<16}",
+            thread.id(),
+            thread.state(),
+            thread.name()
+        );
+
+        if let Some(stack) = thread.stack() {
+            print!(" {:016x}-{:016x}", stack.start(), stack.end());
+        }
+
+        println!();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use crate::state::State;
+use clap::{Parser, Subcommand};
+use fraud_motor_core::debug::WatchKind;
+use std::error::Error;
+
+#[derive(Parser)]
+pub struct Args {
+    #[command(subcommand)]
+    command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+    Write { addr: String, size: usize },
+    Access { addr: String, size: usize },
+    Del { id: usize },
+    List,
+}
+
+pub fn watch(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
+    match args.command {
+        Commands::Write { addr, size } => watch_add(state, &addr, size, WatchKind::Write)?,
+        Commands::Access { addr, size } => watch_add(state, &addr, size, WatchKind::Access)?,
+        Commands::Del { id } => {
+            if let Err(err) = state
+                .debugger()
+                .and_then(|debugger| Ok(debugger.unwatch(id)?))
+            {
+                println!("{}: {}", id, err);
+            }
+        }
+        Commands::List => {
+            let Some(debugger) = &state.debugger else {
+                return Ok(());
+            };
+
+            let watchpoints = match debugger.watchpoints() {
+                Ok(watchpoints) => watchpoints,
+                Err(err) => {
+                    println!("watch: {}", err);
+
+                    return Ok(());
+                }
+            };
+
+            for (id, watchpoint) in watchpoints {
+                println!(
+                    "{} {:?} {:016x} {}{}",
+                    id,
+                    watchpoint.kind(),
+                    watchpoint.addr(),
+                    watchpoint.size(),
+                    state.describe(watchpoint.addr()),
+                );
+
+                for (&ip, &count) in watchpoint.hits() {
+                    println!("  {:>8} {:016x}{}", count, ip, state.locate(ip));
+                }
+            }
+        }
+    };
+
+    Ok(())
+}
+
+fn watch_add(
+    state: &mut State,
+    addr: &str,
+    size: usize,
+    kind: WatchKind,
+) -> Result<(), Box<dyn Error>> {
+    let addr_value = match state.resolve(addr) {
+        Ok(addr) => addr,
+        Err(err) => {
+            println!("{}: {}", addr, err);
+
+            return Ok(());
+        }
+    };
+
+    match state
+        .debugger()
+        .and_then(|debugger| Ok(debugger.watch(addr_value, size, kind)?))
+    {
+        Ok(id) => println!("{}", id),
+        Err(err) => println!("{}: {}", addr, err),
+    }
+
+    Ok(())
+}
@@ -1,10 +1,14 @@
 use crate::state::State;
 use clap::{Parser, Subcommand};
+use fraud_motor_core::value;
 use std::error::Error;
 
 #[derive(Parser)]
 pub struct Args {
-    addr: usize,
+    addr: String,
+    /// Write the value as big-endian
+    #[arg(long)]
+    be: bool,
     #[command(subcommand)]
     value: Value,
 }
@@ -15,27 +19,49 @@ pub enum Value {
     U16 { value: u16 },
     U32 { value: u32 },
     U64 { value: u64 },
+    U128 { value: u128 },
     I8 { value: i8 },
     I16 { value: i16 },
     I32 { value: i32 },
     I64 { value: i64 },
+    I128 { value: i128 },
     F32 { value: f32 },
     F64 { value: f64 },
 }
 
 pub fn write(state: &mut State, args: Args) -> Result<(), Box<dyn Error>> {
-    match args.value {
-        Value::U8 { value } => state.memory.write(&value.to_ne_bytes(), args.addr)?,
-        Value::U16 { value } => state.memory.write(&value.to_ne_bytes(), args.addr)?,
-        Value::U32 { value } => state.memory.write(&value.to_ne_bytes(), args.addr)?,
-        Value::U64 { value } => state.memory.write(&value.to_ne_bytes(), args.addr)?,
-        Value::I8 { value } => state.memory.write(&value.to_ne_bytes(), args.addr)?,
-        Value::I16 { value } => state.memory.write(&value.to_ne_bytes(), args.addr)?,
-        Value::I32 { value } => state.memory.write(&value.to_ne_bytes(), args.addr)?,
-        Value::I64 { value } => state.memory.write(&value.to_ne_bytes(), args.addr)?,
-        Value::F32 { value } => state.memory.write(&value.to_ne_bytes(), args.addr)?,
-        Value::F64 { value } => state.memory.write(&value.to_ne_bytes(), args.addr)?,
+    let addr = match state.resolve(&args.addr) {
+        Ok(addr) => addr,
+        Err(err) => {
+            println!("{}: {}", args.addr, err);
+
+            return Ok(());
+        }
+    };
+
+    let value = match args.value {
+        Value::U8 { value } => value::Value::U8(value),
+        Value::U16 { value } => value::Value::U16(value),
+        Value::U32 { value } => value::Value::U32(value),
+        Value::U64 { value } => value::Value::U64(value),
+        Value::U128 { value } => value::Value::U128(value),
+        Value::I8 { value } => value::Value::I8(value),
+        Value::I16 { value } => value::Value::I16(value),
+        Value::I32 { value } => value::Value::I32(value),
+        Value::I64 { value } => value::Value::I64(value),
+        Value::I128 { value } => value::Value::I128(value),
+        Value::F32 { value } => value::Value::F32(value),
+        Value::F64 { value } => value::Value::F64(value),
     };
+    let bytes = if args.be {
+        value.to_be_bytes()
+    } else {
+        value.to_bytes()
+    };
+
+    if let Err(err) = state.memory.write(&bytes, addr) {
+        println!("{}: {}", args.addr, err);
+    }
 
     Ok(())
 }
@@ -0,0 +1,542 @@
+use crate::error::Result;
+use iced_x86::{
+    Code, Encoder, IcedError, Instruction, MemoryOperand, Mnemonic, OpCodeOperandKind, Register,
+};
+use std::{io, result};
+
+#[derive(Clone, Copy, Debug)]
+enum Operand {
+    Register(Register),
+    Memory(Memory),
+    Immediate(i64),
+}
+
+#[derive(Clone, Copy, Debug)]
+struct Memory {
+    base: Register,
+    index: Register,
+    scale: u32,
+    displacement: i64,
+    segment: Register,
+    size: Option<usize>,
+    relative: bool,
+}
+
+#[derive(Default)]
+struct Prefixes {
+    lock: bool,
+    rep: bool,
+    repne: bool,
+}
+
+const SIZES: [(&str, usize); 8] = [
+    ("byte", 1),
+    ("word", 2),
+    ("dword", 4),
+    ("qword", 8),
+    ("tbyte", 10),
+    ("xmmword", 16),
+    ("ymmword", 32),
+    ("zmmword", 64),
+];
+
+const ALIASES: [(&str, &str); 16] = [
+    ("jz", "je"),
+    ("jnz", "jne"),
+    ("jc", "jb"),
+    ("jnae", "jb"),
+    ("jnc", "jae"),
+    ("jnb", "jae"),
+    ("jna", "jbe"),
+    ("jnbe", "ja"),
+    ("jnge", "jl"),
+    ("jnl", "jge"),
+    ("jng", "jle"),
+    ("jnle", "jg"),
+    ("jpe", "jp"),
+    ("jpo", "jnp"),
+    ("sal", "shl"),
+    ("wait", "fwait"),
+];
+
+pub fn assemble(text: &str, addr: usize) -> Result<Vec<u8>> {
+    assemble_with(text, addr, |_| None)
+}
+
+pub fn assemble_with<F>(text: &str, addr: usize, resolve: F) -> Result<Vec<u8>>
+where
+    F: Fn(&str) -> Option<usize>,
+{
+    let mut code = Vec::new();
+
+    for line in text.split([';', '\n']).map(str::trim) {
+        if line.is_empty() {
+            continue;
+        }
+
+        let ip = addr + code.len();
+        let bytes = assemble_line(line, ip, &resolve)
+            .map_err(|err| invalid_input(format!("{}: {}", line, err)))?;
+
+        code.extend(bytes);
+    }
+
+    Ok(code)
+}
+
+fn assemble_line<F>(line: &str, ip: usize, resolve: &F) -> result::Result<Vec<u8>, String>
+where
+    F: Fn(&str) -> Option<usize>,
+{
+    let mut prefixes = Prefixes::default();
+    let mut rest = line;
+
+    let mnemonic = loop {
+        let (word, tail) = rest.split_once(char::is_whitespace).unwrap_or((rest, ""));
+        let word = word.to_ascii_lowercase();
+
+        rest = tail.trim();
+
+        match word.as_str() {
+            "lock" => prefixes.lock = true,
+            "rep" | "repe" | "repz" => prefixes.rep = true,
+            "repne" | "repnz" => prefixes.repne = true,
+            _ => break word,
+        }
+    };
+
+    let name = ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == mnemonic)
+        .map_or(mnemonic.as_str(), |(_, name)| name);
+
+    let mnemonic = Mnemonic::values()
+        .find(|value| format!("{:?}", value).eq_ignore_ascii_case(name))
+        .ok_or_else(|| "unknown mnemonic".to_owned())?;
+
+    let operands = split_operands(rest)
+        .into_iter()
+        .map(|operand| parse_operand(operand, resolve))
+        .collect::<result::Result<Vec<_>, _>>()?;
+
+    let memory = operands.iter().find_map(|operand| match operand {
+        Operand::Memory(memory) => Some(memory),
+        _ => None,
+    });
+    let mut best: Option<Vec<u8>> = None;
+    let mut sizes = Vec::new();
+
+    for code in Code::values().filter(|code| code.mnemonic() == mnemonic) {
+        let op_code = code.op_code();
+        let size = op_code.memory_size().size();
+
+        if !op_code.is_instruction()
+            || !op_code.mode64()
+            || op_code.op_count() as usize != operands.len()
+            || memory.is_some_and(|memory| memory.size.is_some_and(|expected| expected != size))
+        {
+            continue;
+        }
+
+        let Some(bytes) = encode(code, &operands, &prefixes, ip) else {
+            continue;
+        };
+
+        if !sizes.contains(&size) {
+            sizes.push(size);
+        }
+
+        if best.as_ref().is_none_or(|best| bytes.len() < best.len()) {
+            best = Some(bytes);
+        }
+    }
+
+    if memory.is_some_and(|memory| memory.size.is_none()) && sizes.len() > 1 {
+        return Err("ambiguous operand size".to_owned());
+    }
+
+    best.ok_or_else(|| "invalid operands".to_owned())
+}
+
+fn encode(code: Code, operands: &[Operand], prefixes: &Prefixes, ip: usize) -> Option<Vec<u8>> {
+    let mut len = 0;
+
+    loop {
+        let operands: Vec<_> = operands
+            .iter()
+            .map(|&operand| match operand {
+                Operand::Memory(memory) if memory.relative => Operand::Memory(Memory {
+                    displacement: memory.displacement.wrapping_add((ip + len) as i64),
+                    ..memory
+                }),
+                operand => operand,
+            })
+            .collect();
+
+        let mut instruction = create(code, &operands)?;
+        let mut encoder = Encoder::new(64);
+
+        instruction.set_has_lock_prefix(prefixes.lock);
+        instruction.set_has_rep_prefix(prefixes.rep);
+        instruction.set_has_repne_prefix(prefixes.repne);
+
+        let encoded = encoder.encode(&instruction, ip as u64).ok()?;
+        let relative = operands
+            .iter()
+            .any(|operand| matches!(operand, Operand::Memory(memory) if memory.relative));
+
+        if !relative || encoded == len {
+            return Some(encoder.take_buffer());
+        }
+
+        len = encoded;
+    }
+}
+
+fn create(code: Code, operands: &[Operand]) -> Option<Instruction> {
+    use Operand::{Immediate as I, Memory as M, Register as R};
+
+    let branch = matches!(
+        code.op_code().op_kind(0),
+        OpCodeOperandKind::br64_1 | OpCodeOperandKind::br64_4
+    );
+
+    match *operands {
+        [] => Some(Instruction::with(code)),
+        [I(a)] if branch => Instruction::with_branch(code, a as u64).ok(),
+        [R(a)] => Instruction::with1(code, a).ok(),
+        [M(a)] => Instruction::with1(code, memory(a)).ok(),
+        [I(a)] => with_immediate(
+            a,
+            |a| Instruction::with1(code, a),
+            |a| Instruction::with1(code, a),
+        ),
+        [R(a), R(b)] => Instruction::with2(code, a, b).ok(),
+        [R(a), M(b)] => Instruction::with2(code, a, memory(b)).ok(),
+        [R(a), I(b)] => Instruction::with2(code, a, b).ok(),
+        [M(a), R(b)] => Instruction::with2(code, memory(a), b).ok(),
+        [M(a), I(b)] => with_immediate(
+            b,
+            |b| Instruction::with2(code, memory(a), b),
+            |b| Instruction::with2(code, memory(a), b),
+        ),
+        [I(a), R(b)] => with_immediate(
+            a,
+            |a| Instruction::with2(code, a, b),
+            |a| Instruction::with2(code, a, b),
+        ),
+        [I(a), I(b)] => {
+            Instruction::with2(code, u32::try_from(a).ok()?, u32::try_from(b).ok()?).ok()
+        }
+        [R(a), R(b), R(c)] => Instruction::with3(code, a, b, c).ok(),
+        [R(a), R(b), M(c)] => Instruction::with3(code, a, b, memory(c)).ok(),
+        [R(a), R(b), I(c)] => with_immediate(
+            c,
+            |c| Instruction::with3(code, a, b, c),
+            |c| Instruction::with3(code, a, b, c),
+        ),
+        [R(a), M(b), R(c)] => Instruction::with3(code, a, memory(b), c).ok(),
+        [R(a), M(b), I(c)] => with_immediate(
+            c,
+            |c| Instruction::with3(code, a, memory(b), c),
+            |c| Instruction::with3(code, a, memory(b), c),
+        ),
+        [M(a), R(b), R(c)] => Instruction::with3(code, memory(a), b, c).ok(),
+        [M(a), R(b), I(c)] => with_immediate(
+            c,
+            |c| Instruction::with3(code, memory(a), b, c),
+            |c| Instruction::with3(code, memory(a), b, c),
+        ),
+        _ => None,
+    }
+}
+
+fn with_immediate<S, U>(value: i64, signed: S, unsigned: U) -> Option<Instruction>
+where
+    S: FnOnce(i32) -> result::Result<Instruction, IcedError>,
+    U: FnOnce(u32) -> result::Result<Instruction, IcedError>,
+{
+    match i32::try_from(value) {
+        Ok(value) => signed(value).ok(),
+        Err(_) => unsigned(u32::try_from(value).ok()?).ok(),
+    }
+}
+
+fn memory(memory: Memory) -> MemoryOperand {
+    let displ_size = if memory.displacement == 0 && memory.base != Register::RIP {
+        0
+    } else {
+        1
+    };
+
+    MemoryOperand::new(
+        memory.base,
+        memory.index,
+        memory.scale,
+        memory.displacement,
+        displ_size,
+        false,
+        memory.segment,
+    )
+}
+
+fn split_operands(text: &str) -> Vec<&str> {
+    let mut operands = Vec::new();
+    let mut depth = 0;
+    let mut start = 0;
+
+    for (i, c) in text.char_indices() {
+        match c {
+            '[' => depth += 1,
+            ']' => depth -= 1,
+            ',' if depth == 0 => {
+                operands.push(text[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+
+    if !text[start..].trim().is_empty() {
+        operands.push(text[start..].trim());
+    }
+
+    operands
+}
+
+fn parse_operand<F>(text: &str, resolve: &F) -> result::Result<Operand, String>
+where
+    F: Fn(&str) -> Option<usize>,
+{
+    let mut text = text;
+    let mut size = None;
+
+    if let Some((word, rest)) = text.split_once(char::is_whitespace) {
+        if let Some(&(_, value)) = SIZES
+            .iter()
+            .find(|(name, _)| word.eq_ignore_ascii_case(name))
+        {
+            let rest = rest.trim_start();
+
+            size = Some(value);
+            text = match rest.get(..3) {
+                Some(ptr) if ptr.eq_ignore_ascii_case("ptr") => rest[3..].trim_start(),
+                _ => rest,
+            };
+        }
+    }
+
+    if let Some(open) = text.find('[') {
+        let inner = text[open + 1..]
+            .strip_suffix(']')
+            .ok_or_else(|| format!("{}: unterminated memory operand", text))?;
+        let segment = match text[..open].trim().strip_suffix(':') {
+            Some(name) => register(name).ok_or_else(|| format!("{}: unknown segment", name))?,
+            None => Register::None,
+        };
+
+        return parse_memory(inner, segment, size, resolve).map(Operand::Memory);
+    }
+
+    if size.is_some() {
+        return Err(format!("{}: size specifier on a non-memory operand", text));
+    }
+
+    match register(text) {
+        Some(register) => Ok(Operand::Register(register)),
+        None => parse_expression(text, resolve).map(Operand::Immediate),
+    }
+}
+
+fn parse_memory<F>(
+    text: &str,
+    segment: Register,
+    size: Option<usize>,
+    resolve: &F,
+) -> result::Result<Memory, String>
+where
+    F: Fn(&str) -> Option<usize>,
+{
+    let mut memory = Memory {
+        base: Register::None,
+        index: Register::None,
+        scale: 1,
+        displacement: 0,
+        segment,
+        size,
+        relative: false,
+    };
+
+    for (negative, term) in terms(text) {
+        let (name, scale) = match term.split_once('*') {
+            Some((name, scale)) => (name.trim(), Some(scale.trim())),
+            None => (term, None),
+        };
+
+        let Some(reg) = register(name) else {
+            let value = parse_expression(term, resolve)?;
+
+            memory.displacement = if negative {
+                memory.displacement.wrapping_sub(value)
+            } else {
+                memory.displacement.wrapping_add(value)
+            };
+
+            continue;
+        };
+
+        if negative {
+            return Err(format!("{}: cannot subtract a register", term));
+        }
+
+        match scale {
+            Some(scale) => {
+                memory.scale = scale
+                    .parse()
+                    .ok()
+                    .filter(|scale| matches!(scale, 1 | 2 | 4 | 8))
+                    .ok_or_else(|| format!("{}: invalid scale", scale))?;
+                memory.index = reg;
+            }
+            None if memory.base == Register::None => memory.base = reg,
+            None if memory.index == Register::None => memory.index = reg,
+            None => return Err(format!("{}: too many registers", text)),
+        }
+    }
+
+    if memory.base == Register::RIP {
+        memory.relative = true;
+    } else if memory.base == Register::None
+        && memory.index == Register::None
+        && memory.segment == Register::None
+    {
+        memory.base = Register::RIP;
+    }
+
+    Ok(memory)
+}
+
+fn parse_expression<F>(text: &str, resolve: &F) -> result::Result<i64, String>
+where
+    F: Fn(&str) -> Option<usize>,
+{
+    let mut value: i64 = 0;
+
+    for (negative, term) in terms(text) {
+        let term_value = parse_number(term)
+            .or_else(|| resolve(term).map(|addr| addr as i64))
+            .ok_or_else(|| format!("{}: unknown symbol or invalid number", term))?;
+
+        value = if negative {
+            value.wrapping_sub(term_value)
+        } else {
+            value.wrapping_add(term_value)
+        };
+    }
+
+    Ok(value)
+}
+
+fn parse_number(text: &str) -> Option<i64> {
+    let (digits, radix) = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+        Some(hex) => (hex, 16),
+        None => match text.strip_suffix(['h', 'H']) {
+            Some(hex) if hex.starts_with(|c: char| c.is_ascii_digit()) => (hex, 16),
+            _ => (text, 10),
+        },
+    };
+
+    u64::from_str_radix(digits, radix)
+        .ok()
+        .map(|value| value as i64)
+}
+
+fn terms(text: &str) -> Vec<(bool, &str)> {
+    let mut terms = Vec::new();
+    let mut negative = text.trim_start().starts_with('-');
+    let mut start = if negative {
+        text.find('-').unwrap() + 1
+    } else {
+        0
+    };
+
+    for (i, c) in text.char_indices().skip(start) {
+        if c == '+' || c == '-' {
+            terms.push((negative, text[start..i].trim()));
+            negative = c == '-';
+            start = i + 1;
+        }
+    }
+
+    terms.push((negative, text[start..].trim()));
+    terms
+}
+
+fn register(name: &str) -> Option<Register> {
+    let name = name.trim();
+
+    Register::values()
+        .filter(|&register| register != Register::None)
+        .find(|register| format!("{:?}", register).eq_ignore_ascii_case(name))
+}
+
+fn invalid_input(msg: String) -> crate::error::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, msg).into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assemble() {
+        let cases: [(&str, &[u8]); 15] = [
+            ("nop", &[0x90]),
+            ("ret", &[0xc3]),
+            ("mov rax, rbx", &[0x48, 0x89, 0xd8]),
+            ("mov eax, 1", &[0xb8, 0x01, 0x00, 0x00, 0x00]),
+            ("add rsp, 8", &[0x48, 0x83, 0xc4, 0x08]),
+            (
+                "mov rax, 0x1122334455667788",
+                &[0x48, 0xb8, 0x88, 0x77, 0x66, 0x55, 0x44, 0x33, 0x22, 0x11],
+            ),
+            ("jmp 0x1010", &[0xeb, 0x0e]),
+            ("jz 0x1000", &[0x74, 0xfe]),
+            ("call 0x2000", &[0xe8, 0xfb, 0x0f, 0x00, 0x00]),
+            (
+                "mov dword ptr [rax+rbx*4+0x10], 5",
+                &[0xc7, 0x44, 0x98, 0x10, 0x05, 0x00, 0x00, 0x00],
+            ),
+            (
+                "mov rax, [rip+0x10]",
+                &[0x48, 0x8b, 0x05, 0x10, 0x00, 0x00, 0x00],
+            ),
+            (
+                "lea rcx, [0x2000]",
+                &[0x48, 0x8d, 0x0d, 0xf9, 0x0f, 0x00, 0x00],
+            ),
+            ("lock xadd [rdi], eax", &[0xf0, 0x0f, 0xc1, 0x07]),
+            (
+                "mov rax, qword ptr fs:[0x28]",
+                &[0x64, 0x48, 0x8b, 0x04, 0x25, 0x28, 0x00, 0x00, 0x00],
+            ),
+            ("push rbp; mov rbp, rsp", &[0x55, 0x48, 0x89, 0xe5]),
+        ];
+
+        for (text, bytes) in cases {
+            assert_eq!(assemble(text, 0x1000).unwrap(), bytes, "{}", text);
+        }
+
+        let resolve = |name: &str| (name == "target").then_some(0x1100);
+        assert_eq!(
+            assemble_with("call target+0x10", 0x1000, resolve).unwrap(),
+            [0xe8, 0x0b, 0x01, 0x00, 0x00]
+        );
+
+        assert!(assemble("inc [rax]", 0x1000).is_err());
+        assert!(assemble("bogus rax", 0x1000).is_err());
+        assert!(assemble("mov rax", 0x1000).is_err());
+        assert!(assemble("and rax, 0xffffffff", 0x1000).is_err());
+        assert!(assemble("call missing", 0x1000).is_err());
+    }
+}
@@ -0,0 +1,298 @@
+use crate::error::{Error, Result};
+use crate::process::Process;
+use crate::sys::debug as debug_imp;
+use crate::thread::Registers;
+use std::collections::{BTreeMap, VecDeque};
+use std::io;
+
+#[cfg_attr(not(target_arch = "x86_64"), allow(dead_code))]
+const BREAKPOINT_LOG_SIZE: usize = 1024;
+
+pub struct Debugger(debug_imp::Debugger);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WatchKind {
+    Write,
+    Access,
+}
+
+#[derive(Clone, Debug)]
+pub struct Watchpoint {
+    addr: usize,
+    size: usize,
+    kind: WatchKind,
+    hits: BTreeMap<usize, usize>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Breakpoint {
+    addr: usize,
+    original: u8,
+    auto_continue: bool,
+    hits: usize,
+    log: VecDeque<BreakpointHit>,
+}
+
+#[derive(Clone, Debug)]
+pub struct BreakpointHit {
+    thread: u32,
+    registers: Option<Registers>,
+}
+
+impl Debugger {
+    pub fn attach(process: &Process) -> Result<Debugger> {
+        debug_imp::Debugger::attach(process.id()).map(Debugger)
+    }
+
+    pub fn watch(&self, addr: usize, size: usize, kind: WatchKind) -> Result<usize> {
+        if !matches!(size, 1 | 2 | 4 | 8) || !addr.is_multiple_of(size) {
+            return Err(Error::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "watchpoint must be 1, 2, 4 or 8 bytes and aligned to its size",
+            )));
+        }
+
+        self.0.watch(Watchpoint {
+            addr,
+            size,
+            kind,
+            hits: BTreeMap::new(),
+        })
+    }
+
+    pub fn unwatch(&self, id: usize) -> Result<()> {
+        self.0.unwatch(id)
+    }
+
+    pub fn watchpoints(&self) -> Result<Vec<(usize, Watchpoint)>> {
+        self.0.watchpoints()
+    }
+
+    pub fn add_breakpoint(&self, addr: usize, auto_continue: bool) -> Result<()> {
+        self.0.add_breakpoint(addr, auto_continue)
+    }
+
+    pub fn remove_breakpoint(&self, addr: usize) -> Result<()> {
+        self.0.remove_breakpoint(addr)
+    }
+
+    pub fn breakpoints(&self) -> Result<Vec<Breakpoint>> {
+        self.0.breakpoints()
+    }
+
+    pub fn resume(&self) -> Result<()> {
+        self.0.resume()
+    }
+}
+
+impl Watchpoint {
+    pub fn addr(&self) -> usize {
+        self.addr
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn kind(&self) -> WatchKind {
+        self.kind
+    }
+
+    pub fn hits(&self) -> &BTreeMap<usize, usize> {
+        &self.hits
+    }
+
+    #[cfg_attr(not(target_arch = "x86_64"), allow(dead_code))]
+    pub(crate) fn hit(&mut self, ip: usize) {
+        *self.hits.entry(ip).or_default() += 1;
+    }
+}
+
+impl Breakpoint {
+    #[cfg_attr(not(target_arch = "x86_64"), allow(dead_code))]
+    pub(crate) fn new(addr: usize, original: u8, auto_continue: bool) -> Breakpoint {
+        Breakpoint {
+            addr,
+            original,
+            auto_continue,
+            hits: 0,
+            log: VecDeque::new(),
+        }
+    }
+
+    pub fn addr(&self) -> usize {
+        self.addr
+    }
+
+    pub fn original(&self) -> u8 {
+        self.original
+    }
+
+    pub fn auto_continue(&self) -> bool {
+        self.auto_continue
+    }
+
+    pub fn hits(&self) -> usize {
+        self.hits
+    }
+
+    pub fn log(&self) -> &VecDeque<BreakpointHit> {
+        &self.log
+    }
+
+    #[cfg_attr(not(target_arch = "x86_64"), allow(dead_code))]
+    pub(crate) fn hit(&mut self, thread: u32, registers: Option<Registers>) {
+        if self.log.len() == BREAKPOINT_LOG_SIZE {
+            self.log.pop_front();
+        }
+
+        self.hits += 1;
+        self.log.push_back(BreakpointHit { thread, registers });
+    }
+}
+
+impl BreakpointHit {
+    pub fn thread(&self) -> u32 {
+        self.thread
+    }
+
+    pub fn registers(&self) -> Option<&Registers> {
+        self.registers.as_ref()
+    }
+}
+
+#[cfg(all(test, target_os = "linux", target_arch = "x86_64"))]
+mod tests {
+    use super::*;
+    use crate::memory::Memory;
+    use crate::process::State;
+    use crate::symbol::Symbols;
+    use crate::testing::Child;
+    use iced_x86::{Decoder, DecoderOptions, OpKind};
+    use std::time::Duration;
+    use std::{hint, ptr, thread};
+
+    static mut WATCH_TARGET: u32 = 0;
+
+    #[inline(never)]
+    fn watch_target_writer() -> ! {
+        let mut i = 0;
+
+        loop {
+            unsafe {
+                WATCH_TARGET = i;
+                libc::usleep(1000);
+            }
+
+            i += 1;
+        }
+    }
+
+    #[test]
+    fn test_debugger_watch() {
+        let child = Child::fork(watch_target_writer);
+        let proc = Process::open(child.id()).unwrap();
+        let debugger = Debugger::attach(&proc).unwrap();
+        let addr = ptr::addr_of!(WATCH_TARGET) as usize;
+
+        assert!(debugger.watch(addr + 1, 4, WatchKind::Write).is_err());
+
+        let id = debugger.watch(addr, 4, WatchKind::Write).unwrap();
+        thread::sleep(Duration::from_millis(100));
+
+        let watchpoints = debugger.watchpoints().unwrap();
+        let (_, watchpoint) = watchpoints.iter().find(|(i, _)| *i == id).unwrap();
+        let symbols = Symbols::load(&proc).unwrap();
+        let writer = watch_target_writer as fn() -> ! as usize;
+        let symbol = symbols.lookup(writer).unwrap().symbol();
+        let code = unsafe { std::slice::from_raw_parts(writer as *const u8, symbol.size()) };
+        let store = Decoder::with_ip(64, code, writer as u64, DecoderOptions::NONE)
+            .into_iter()
+            .find(|instruction| {
+                instruction.op0_kind() == OpKind::Memory
+                    && instruction.is_ip_rel_memory_operand()
+                    && instruction.ip_rel_memory_address() == addr as u64
+            })
+            .unwrap();
+
+        assert_eq!(watchpoint.addr(), addr);
+        assert!(watchpoint.hits()[&(store.ip() as usize)] > 1);
+        assert_eq!(watchpoint.hits().len(), 1);
+
+        debugger.unwatch(id).unwrap();
+        assert!(debugger.watchpoints().unwrap().is_empty());
+        assert!(debugger.unwatch(id).is_err());
+    }
+
+    #[inline(never)]
+    fn break_target(i: u64) -> u64 {
+        hint::black_box(i)
+    }
+
+    fn break_target_caller() -> ! {
+        let mut i = 0;
+
+        loop {
+            break_target(i);
+            unsafe { libc::usleep(1000) };
+            i += 1;
+        }
+    }
+
+    #[test]
+    fn test_debugger_breakpoints() {
+        let child = Child::fork(break_target_caller);
+        let proc = Process::open(child.id()).unwrap();
+        let memory = Memory::options().read(true).open(child.id()).unwrap();
+        let debugger = Debugger::attach(&proc).unwrap();
+        let addr = break_target as fn(u64) -> u64 as usize;
+        let data = ptr::addr_of!(WATCH_TARGET) as usize;
+        let mut original = [0];
+
+        memory.read(&mut original, addr).unwrap();
+        assert!(debugger.add_breakpoint(data, true).is_err());
+
+        debugger.add_breakpoint(addr, true).unwrap();
+        assert!(debugger.add_breakpoint(addr, true).is_err());
+        thread::sleep(Duration::from_millis(100));
+
+        let breakpoints = debugger.breakpoints().unwrap();
+        let log = breakpoints[0].log();
+
+        assert_eq!(breakpoints[0].original(), original[0]);
+        assert!(breakpoints[0].hits() > 1);
+        assert_eq!(breakpoints[0].hits(), log.len());
+
+        for (prev, hit) in log.iter().zip(log.iter().skip(1)) {
+            let registers = hit.registers().unwrap();
+
+            assert_eq!(hit.thread(), child.id());
+            assert_eq!(registers.rip as usize, addr);
+            assert_eq!(registers.rdi, prev.registers().unwrap().rdi + 1);
+        }
+
+        debugger.remove_breakpoint(addr).unwrap();
+        debugger.add_breakpoint(addr, false).unwrap();
+
+        while debugger.breakpoints().unwrap()[0].hits() == 0 {
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        thread::sleep(Duration::from_millis(20));
+        assert_eq!(debugger.breakpoints().unwrap()[0].hits(), 1);
+        assert_eq!(proc.info().unwrap().state(), State::Traced);
+
+        debugger.resume().unwrap();
+
+        while debugger.breakpoints().unwrap()[0].hits() < 2 {
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        let mut restored = [0];
+
+        debugger.remove_breakpoint(addr).unwrap();
+        memory.read(&mut restored, addr).unwrap();
+        assert_eq!(restored, original);
+        assert!(debugger.remove_breakpoint(addr).is_err());
+    }
+}
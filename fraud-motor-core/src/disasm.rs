@@ -0,0 +1,220 @@
+use crate::dump::ProcessDump;
+use crate::error::{Error, Result};
+use crate::memory::Memory;
+use crate::process::{Module, ModuleAddress};
+use crate::symbol::Symbols;
+use iced_x86::{Decoder, DecoderError, DecoderOptions, Formatter, IntelFormatter, OpKind};
+
+const MAX_INSTRUCTION_SIZE: usize = 15;
+
+pub struct Disassembler<'a> {
+    modules: &'a [Module],
+    symbols: &'a Symbols,
+}
+
+#[derive(Clone, Debug)]
+pub struct Instruction {
+    addr: usize,
+    bytes: Vec<u8>,
+    text: String,
+    target: Option<usize>,
+    annotation: Option<String>,
+}
+
+impl<'a> Disassembler<'a> {
+    pub fn new(modules: &'a [Module], symbols: &'a Symbols) -> Disassembler<'a> {
+        Disassembler { modules, symbols }
+    }
+
+    pub fn decode(&self, code: &[u8], addr: usize, count: usize) -> Vec<Instruction> {
+        let mut decoder = Decoder::with_ip(64, code, addr as u64, DecoderOptions::NONE);
+        let mut formatter = IntelFormatter::new();
+        let mut instructions = Vec::new();
+
+        formatter.options_mut().set_hex_prefix("0x");
+        formatter.options_mut().set_hex_suffix("");
+        formatter.options_mut().set_uppercase_hex(false);
+        formatter.options_mut().set_branch_leading_zeros(false);
+        formatter
+            .options_mut()
+            .set_space_after_operand_separator(true);
+
+        while decoder.can_decode() && instructions.len() < count {
+            let offset = decoder.position();
+            let instruction = decoder.decode();
+
+            if instruction.is_invalid() && decoder.last_error() == DecoderError::NoMoreBytes {
+                break;
+            }
+
+            let mut text = String::new();
+            let target = if instruction.is_ip_rel_memory_operand() {
+                Some(instruction.ip_rel_memory_address() as usize)
+            } else if (0..instruction.op_count()).any(|i| {
+                matches!(
+                    instruction.op_kind(i),
+                    OpKind::NearBranch16 | OpKind::NearBranch32 | OpKind::NearBranch64
+                )
+            }) {
+                Some(instruction.near_branch_target() as usize)
+            } else {
+                None
+            };
+
+            if instruction.is_invalid() {
+                text.push_str("(bad)");
+            } else {
+                formatter.format(&instruction, &mut text);
+            }
+
+            instructions.push(Instruction {
+                addr: instruction.ip() as usize,
+                bytes: code[offset..offset + instruction.len()].to_owned(),
+                text,
+                target,
+                annotation: target.and_then(|target| self.annotate(target)),
+            });
+        }
+
+        instructions
+    }
+
+    pub fn read(&self, memory: &Memory, addr: usize, count: usize) -> Result<Vec<Instruction>> {
+        let mut code = vec![0; count * MAX_INSTRUCTION_SIZE];
+        let len = match memory.read_partial(&mut code, addr)?.first() {
+            Some(range) if range.start == addr => range.len(),
+            _ => return Err(Error::Unmapped(addr)),
+        };
+
+        Ok(self.decode(&code[..len], addr, count))
+    }
+
+    pub fn read_dump(
+        &self,
+        dump: &ProcessDump,
+        addr: usize,
+        count: usize,
+    ) -> Result<Vec<Instruction>> {
+        let mut view = dump.view();
+        let code = view.data(addr).ok_or(Error::Unmapped(addr))?;
+        let len = code.len().min(count * MAX_INSTRUCTION_SIZE);
+
+        Ok(self.decode(&code[..len], addr, count))
+    }
+
+    pub fn annotate(&self, addr: usize) -> Option<String> {
+        let module = ModuleAddress::from_addr(self.modules, addr);
+        let symbol = self.symbols.lookup(addr);
+
+        match (module, symbol) {
+            (Some(module), Some(symbol)) => Some(format!("{} {}", module, symbol)),
+            (Some(module), None) => Some(module.to_string()),
+            (None, Some(symbol)) => Some(symbol.to_string()),
+            (None, None) => None,
+        }
+    }
+}
+
+impl Instruction {
+    pub fn addr(&self) -> usize {
+        self.addr
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn target(&self) -> Option<usize> {
+        self.target
+    }
+
+    pub fn annotation(&self) -> Option<&str> {
+        self.annotation.as_deref()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process::Process;
+    use std::{hint, process};
+
+    #[inline(never)]
+    fn disasm_target(i: u64) -> u64 {
+        hint::black_box(i) + 1
+    }
+
+    #[inline(never)]
+    fn disasm_caller(i: u64) -> u64 {
+        disasm_target(i) * 2
+    }
+
+    #[test]
+    fn test_disassembler_decode() {
+        let symbols = Symbols::from_modules(&[]);
+        let disasm = Disassembler::new(&[], &symbols);
+        let code = [
+            0x48, 0x8b, 0x05, 0x10, 0x00, 0x00, 0x00, 0xe8, 0xf4, 0xff, 0xff, 0xff, 0x90, 0xc3,
+            0x06, 0x48,
+        ];
+
+        let instructions = disasm.decode(&code, 0x1000, 16);
+        let text: Vec<_> = instructions.iter().map(Instruction::text).collect();
+
+        assert_eq!(
+            text,
+            ["mov rax, [0x1017]", "call 0x1000", "nop", "ret", "(bad)"]
+        );
+        assert_eq!(instructions[0].target(), Some(0x1017));
+        assert_eq!(instructions[1].target(), Some(0x1000));
+        assert_eq!(instructions[1].addr(), 0x1007);
+        assert_eq!(instructions[1].bytes(), &code[7..12]);
+        assert_eq!(instructions[2].target(), None);
+        assert_eq!(disasm.decode(&code, 0x1000, 2).len(), 2);
+    }
+
+    #[test]
+    fn test_disassembler_read() {
+        let proc = Process::open(process::id()).unwrap();
+        let memory = Memory::options().read(true).open(process::id()).unwrap();
+        let modules: Vec<_> = proc.modules().unwrap().collect::<Result<_>>().unwrap();
+        let symbols = Symbols::from_modules(&modules);
+        let disasm = Disassembler::new(&modules, &symbols);
+        let caller = disasm_caller as fn(u64) -> u64 as usize;
+        let target = disasm_target as fn(u64) -> u64 as usize;
+
+        hint::black_box(disasm_caller(1));
+
+        let instructions = disasm.read(&memory, caller, 64).unwrap();
+        let call = instructions
+            .iter()
+            .find(|instruction| instruction.target() == Some(target))
+            .unwrap();
+
+        assert_eq!(instructions[0].addr(), caller);
+        assert!(call.text().starts_with("call"));
+        assert!(call.annotation().unwrap().contains("disasm_target"));
+
+        let dump = ProcessDump::new(&memory, &proc, |region| region.permissions().exec()).unwrap();
+        let dumped = disasm.read_dump(&dump, caller, 64).unwrap();
+
+        assert_eq!(dumped.len(), instructions.len());
+        assert!(dumped
+            .iter()
+            .zip(&instructions)
+            .all(|(a, b)| a.text() == b.text() && a.bytes() == b.bytes()));
+        assert!(disasm.read(&memory, 0, 1).is_err());
+    }
+}
@@ -1,24 +1,36 @@
+use crate::error::{Error, Result};
 use crate::memory::Memory;
 use crate::process::{Process, Region};
-use std::io;
+use std::ops::Range;
 use std::sync::{Arc, Mutex, Weak};
 
-pub struct RegionDump(Arc<[u8]>);
+pub struct RegionDump {
+    data: Arc<[u8]>,
+    readable: Box<[Range<usize>]>,
+}
 
-pub struct ProcessDump(Box<[(Region, io::Result<RegionDump>)]>);
+pub struct ProcessDump(Box<[(Region, Result<RegionDump>)]>);
 
 pub struct DumpView<'a> {
-    regions: &'a [(Region, io::Result<RegionDump>)],
+    regions: &'a [(Region, Result<RegionDump>)],
     last: Option<(&'a Region, &'a RegionDump)>,
 }
 
 static REGION_DUMP_POOL: Mutex<Vec<Weak<[u8]>>> = Mutex::new(Vec::new());
 
 impl RegionDump {
-    pub fn new(memory: &Memory, region: &Region) -> io::Result<RegionDump> {
+    pub fn new(memory: &Memory, region: &Region) -> Result<RegionDump> {
         let mut buf = vec![0; region.end() - region.start()];
+        let readable = memory.read_partial(&mut buf, region.start())?;
+
+        if readable.is_empty() {
+            return Err(Error::Unmapped(region.start()));
+        }
 
-        memory.read(&mut buf, region.start())?;
+        let readable = readable
+            .into_iter()
+            .map(|range| range.start - region.start()..range.end - region.start())
+            .collect();
 
         let mut guard = REGION_DUMP_POOL.lock().unwrap();
         let mut pool: Vec<_> = guard.iter().filter_map(Weak::upgrade).collect();
@@ -32,11 +44,29 @@ impl RegionDump {
         };
 
         *guard = pool.iter().map(Arc::downgrade).collect();
-        Ok(RegionDump(arc))
+        Ok(RegionDump {
+            data: arc,
+            readable,
+        })
     }
 
     pub fn data(&self) -> &[u8] {
-        &self.0
+        &self.data
+    }
+
+    pub fn readable(&self) -> &[Range<usize>] {
+        &self.readable
+    }
+
+    pub fn complete(&self) -> bool {
+        self.readable.len() == 1 && self.readable[0] == (0..self.data.len())
+    }
+
+    pub fn get(&self, offset: usize) -> Option<&[u8]> {
+        let i = self.readable.partition_point(|range| range.end <= offset);
+        let range = self.readable.get(i).filter(|range| range.start <= offset)?;
+
+        Some(&self.data[offset..range.end])
     }
 }
 
@@ -45,7 +75,7 @@ impl ProcessDump {
         memory: &Memory,
         process: &Process,
         mut filter: F,
-    ) -> io::Result<ProcessDump> {
+    ) -> Result<ProcessDump> {
         let regions = process
             .regions()?
             .filter(|region| !region.as_ref().is_ok_and(|region| !filter(region)))
@@ -55,16 +85,26 @@ impl ProcessDump {
 
                 Ok((region, dump))
             })
-            .collect::<io::Result<_>>()?;
+            .collect::<Result<_>>()?;
 
         Ok(ProcessDump(regions))
     }
 
-    pub fn regions(&self) -> &[(Region, io::Result<RegionDump>)] {
+    pub fn frozen<F: FnMut(&Region) -> bool>(
+        memory: &Memory,
+        process: &Process,
+        filter: F,
+    ) -> Result<ProcessDump> {
+        let _suspended = process.suspend()?;
+
+        ProcessDump::new(memory, process, filter)
+    }
+
+    pub fn regions(&self) -> &[(Region, Result<RegionDump>)] {
         &self.0
     }
 
-    pub fn view(&self) -> DumpView {
+    pub fn view(&self) -> DumpView<'_> {
         DumpView {
             regions: &self.0,
             last: None,
@@ -76,7 +116,7 @@ impl<'a> DumpView<'a> {
     pub fn data(&mut self, addr: usize) -> Option<&[u8]> {
         if let Some((region, dump)) = self.last {
             if addr >= region.start() && addr < region.end() {
-                return Some(&dump.data()[addr - region.start()..]);
+                return dump.get(addr - region.start());
             }
         }
 
@@ -87,14 +127,23 @@ impl<'a> DumpView<'a> {
             .and_then(|(region, dump)| Some((region, dump.as_ref().ok()?)))?;
 
         self.last = Some((region, dump));
-        Some(&dump.data()[addr - region.start()..])
+        dump.get(addr - region.start())
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(target_os = "linux")]
+    use crate::memory::page_size;
+    use crate::process::State;
+    #[cfg(target_os = "linux")]
+    use std::fs::{self, File};
+    #[cfg(target_os = "linux")]
+    use std::os::unix::io::AsRawFd;
     use std::process;
+    #[cfg(target_os = "linux")]
+    use std::{env, ptr};
 
     fn any_permissions(region: &Region) -> bool {
         region.permissions().read() || region.permissions().write() || region.permissions().exec()
@@ -113,15 +162,21 @@ mod tests {
                 let dump_0 = RegionDump::new(&memory, &region);
                 let dump_1 = RegionDump::new(&memory, &region);
 
-                if let Ok(dump0) = dump_0 {
-                    let dump1 = dump_1.unwrap();
+                match (dump_0, dump_1) {
+                    (Ok(dump0), Ok(dump1)) => {
+                        if dump0.data().as_ptr() != dump1.data().as_ptr() {
+                            assert_ne!(dump0.data(), dump1.data());
+                            assert!(!region.permissions().exec());
+                        }
+                    }
+                    // another test thread can unmap the region between the two reads
+                    (Ok(_), Err(_)) => {
+                        let mut regions = proc.regions().unwrap();
 
-                    if dump0.data().as_ptr() != dump1.data().as_ptr() {
-                        assert_ne!(dump0.data(), dump1.data());
-                        assert!(!region.permissions().exec());
+                        assert!(!regions
+                            .any(|other| other.is_ok_and(|other| other.start() == region.start())));
                     }
-                } else {
-                    assert!(dump_1.is_err());
+                    (Err(_), dump1) => assert!(dump1.is_err()),
                 }
             }
         }
@@ -136,12 +191,80 @@ mod tests {
         let mut view = dump.view();
 
         for (region, dump) in dump.regions() {
-            assert!(any_permissions(&region));
+            assert!(any_permissions(region));
 
             if let Ok(dump) = dump {
+                let readable = &dump.readable()[0];
+
                 assert_eq!(region.end() - region.start(), dump.data().len());
-                assert_eq!(view.data(region.start()).unwrap(), dump.data());
+                assert_eq!(
+                    view.data(region.start() + readable.start).unwrap(),
+                    &dump.data()[readable.clone()]
+                );
             }
         }
     }
+
+    #[test]
+    fn test_process_dump_frozen() {
+        let mut child = process::Command::new("sleep").arg("10").spawn().unwrap();
+        let memory = Memory::options().read(true).open(child.id()).unwrap();
+        let proc = Process::open(child.id()).unwrap();
+        let dump =
+            ProcessDump::frozen(&memory, &proc, |region| region.permissions().write()).unwrap();
+
+        assert!(dump.regions().iter().any(|(_, dump)| dump.is_ok()));
+        assert_ne!(proc.info().unwrap().state(), State::Stopped);
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_region_dump_holes() {
+        let page_size = page_size();
+        let id = process::id();
+        let memory = Memory::options().read(true).open(id).unwrap();
+        let proc = Process::open(id).unwrap();
+        let path = env::temp_dir().join(format!("fraud-motor-dump-{}", id));
+        fs::write(&path, vec![42; page_size]).unwrap();
+
+        let file = File::open(&path).unwrap();
+        let addr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                page_size * 3,
+                libc::PROT_READ,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        } as usize;
+
+        fs::remove_file(&path).unwrap();
+
+        let region = proc
+            .regions()
+            .unwrap()
+            .map(Result::unwrap)
+            .find(|region| region.start() == addr)
+            .unwrap();
+
+        let dump = RegionDump::new(&memory, &region).unwrap();
+        let dump = ProcessDump(vec![(region, Ok(dump))].into());
+        let mut view = dump.view();
+        let (_, region_dump) = &dump.regions()[0];
+        let region_dump = region_dump.as_ref().unwrap();
+
+        assert_eq!(region_dump.data().len(), page_size * 3);
+        assert_eq!(region_dump.readable().len(), 1);
+        assert_eq!(region_dump.readable()[0], 0..page_size);
+        assert!(!region_dump.complete());
+        assert_eq!(view.data(addr + 16).unwrap(), vec![42; page_size - 16]);
+        assert!(view.data(addr + page_size).is_none());
+        assert!(view.data(addr + page_size * 2 + 16).is_none());
+
+        unsafe { libc::munmap(addr as *mut libc::c_void, page_size * 3) };
+    }
 }
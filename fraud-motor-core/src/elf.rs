@@ -0,0 +1,209 @@
+use crate::error::{Error, Result};
+use crate::memory;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+const PT_LOAD: u32 = 1;
+const SHT_SYMTAB: u32 = 2;
+const SHT_DYNSYM: u32 = 11;
+const SHN_UNDEF: u16 = 0;
+const STT_OBJECT: u8 = 1;
+const STT_FUNC: u8 = 2;
+const STT_GNU_IFUNC: u8 = 10;
+
+pub struct Elf {
+    base: u64,
+    end: u64,
+    symbols: Vec<ElfSymbol>,
+}
+
+pub struct ElfSymbol {
+    name: String,
+    value: u64,
+    size: u64,
+    func: bool,
+    dynamic: bool,
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn bytes(&self, offset: u64, len: u64) -> Result<&'a [u8]> {
+        usize::try_from(offset)
+            .ok()
+            .zip(usize::try_from(len).ok())
+            .and_then(|(offset, len)| self.0.get(offset..offset.checked_add(len)?))
+            .ok_or_else(|| invalid_data("truncated elf file"))
+    }
+
+    fn u8(&self, offset: u64) -> Result<u8> {
+        Ok(self.bytes(offset, 1)?[0])
+    }
+
+    fn u16(&self, offset: u64) -> Result<u16> {
+        Ok(u16::from_le_bytes(
+            self.bytes(offset, 2)?.try_into().unwrap(),
+        ))
+    }
+
+    fn u32(&self, offset: u64) -> Result<u32> {
+        Ok(u32::from_le_bytes(
+            self.bytes(offset, 4)?.try_into().unwrap(),
+        ))
+    }
+
+    fn u64(&self, offset: u64) -> Result<u64> {
+        Ok(u64::from_le_bytes(
+            self.bytes(offset, 8)?.try_into().unwrap(),
+        ))
+    }
+
+    fn str(&self, offset: u64) -> Result<String> {
+        let bytes = usize::try_from(offset)
+            .ok()
+            .and_then(|offset| self.0.get(offset..))
+            .ok_or_else(|| invalid_data("truncated elf file"))?;
+
+        let len = bytes
+            .iter()
+            .position(|&byte| byte == 0)
+            .ok_or_else(|| invalid_data("unterminated string"))?;
+
+        Ok(String::from_utf8_lossy(&bytes[..len]).into_owned())
+    }
+}
+
+impl Elf {
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Elf> {
+        Elf::parse(&fs::read(path)?)
+    }
+
+    pub fn parse(data: &[u8]) -> Result<Elf> {
+        let page_size = memory::page_size() as u64;
+        let file = Reader(data);
+
+        if file.bytes(0, 4)? != b"\x7fELF" || file.u8(4)? != 2 || file.u8(5)? != 1 {
+            return Err(invalid_data("not a little endian elf64 file"));
+        }
+
+        let phoff = file.u64(32)?;
+        let shoff = file.u64(40)?;
+        let phentsize = file.u16(54)? as u64;
+        let phnum = file.u16(56)? as u64;
+        let shentsize = file.u16(58)? as u64;
+        let shnum = file.u16(60)? as u64;
+
+        if phoff > data.len() as u64 || shoff > data.len() as u64 {
+            return Err(invalid_data("truncated elf file"));
+        }
+
+        let mut base = u64::MAX;
+        let mut end = 0;
+
+        for i in 0..phnum {
+            let phdr = phoff + i * phentsize;
+
+            if file.u32(phdr)? == PT_LOAD {
+                let vaddr = file.u64(phdr + 16)?;
+                let memsz = file.u64(phdr + 40)?;
+
+                base = base.min(vaddr & !(page_size - 1));
+                end = end.max(vaddr.saturating_add(memsz));
+            }
+        }
+
+        let mut symbols = Vec::new();
+
+        for i in 0..shnum {
+            let shdr = shoff + i * shentsize;
+            let sh_type = file.u32(shdr + 4)?;
+
+            if sh_type != SHT_SYMTAB && sh_type != SHT_DYNSYM {
+                continue;
+            }
+
+            let offset = file.u64(shdr + 24)?;
+            let size = file.u64(shdr + 32)?;
+            let link = file.u32(shdr + 40)? as u64;
+            let entsize = file.u64(shdr + 56)?.max(24);
+            let strtab_shdr = shoff + link * shentsize;
+            let strtab_offset = file.u64(strtab_shdr + 24)?;
+            let strtab_size = file.u64(strtab_shdr + 32)?;
+            let strtab = Reader(file.bytes(strtab_offset, strtab_size)?);
+
+            file.bytes(offset, size)?;
+
+            for sym in (offset..offset + size).step_by(entsize as usize) {
+                let info = file.u8(sym + 4)?;
+                let shndx = file.u16(sym + 6)?;
+                let value = file.u64(sym + 8)?;
+                let kind = info & 0xf;
+
+                if shndx == SHN_UNDEF || value == 0 {
+                    continue;
+                }
+
+                if kind != STT_OBJECT && kind != STT_FUNC && kind != STT_GNU_IFUNC {
+                    continue;
+                }
+
+                let name = strtab.str(file.u32(sym)? as u64)?;
+
+                if !name.is_empty() {
+                    symbols.push(ElfSymbol {
+                        name,
+                        value,
+                        size: file.u64(sym + 16)?,
+                        func: kind != STT_OBJECT,
+                        dynamic: sh_type == SHT_DYNSYM,
+                    });
+                }
+            }
+        }
+
+        Ok(Elf {
+            base: if base == u64::MAX { 0 } else { base },
+            end: end.div_ceil(page_size) * page_size,
+            symbols,
+        })
+    }
+
+    pub fn base(&self) -> u64 {
+        self.base
+    }
+
+    pub fn end(&self) -> u64 {
+        self.end
+    }
+
+    pub fn symbols(&self) -> &[ElfSymbol] {
+        &self.symbols
+    }
+}
+
+impl ElfSymbol {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+
+    pub fn func(&self) -> bool {
+        self.func
+    }
+
+    pub fn dynamic(&self) -> bool {
+        self.dynamic
+    }
+}
+
+fn invalid_data(msg: &str) -> Error {
+    Error::Io(io::Error::new(io::ErrorKind::InvalidData, msg))
+}
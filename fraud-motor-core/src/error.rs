@@ -0,0 +1,66 @@
+use std::fmt::{self, Display, Formatter};
+use std::{error, io, result};
+
+pub type Result<T> = result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    ProcessGone,
+    PermissionDenied,
+    Unmapped(usize),
+    PartialRead { addr: usize, read: usize },
+    Io(io::Error),
+}
+
+impl Error {
+    pub fn kind(&self) -> io::ErrorKind {
+        match self {
+            Error::ProcessGone => io::ErrorKind::NotFound,
+            Error::PermissionDenied => io::ErrorKind::PermissionDenied,
+            Error::Unmapped(_) => io::ErrorKind::InvalidInput,
+            Error::PartialRead { .. } => io::ErrorKind::UnexpectedEof,
+            Error::Io(err) => err.kind(),
+        }
+    }
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Error::ProcessGone => write!(f, "process is gone"),
+            Error::PermissionDenied => write!(f, "permission denied"),
+            Error::Unmapped(addr) => write!(f, "address {:#x} is not mapped", addr),
+            Error::PartialRead { addr, read } => {
+                write!(f, "partial read at {:#x}: {} bytes read", addr, read)
+            }
+            Error::Io(err) => err.fmt(f),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Error {
+        match err.kind() {
+            io::ErrorKind::PermissionDenied => Error::PermissionDenied,
+            _ => Error::Io(err),
+        }
+    }
+}
+
+impl From<Error> for io::Error {
+    fn from(err: Error) -> io::Error {
+        match err {
+            Error::Io(err) => err,
+            err => io::Error::new(err.kind(), err),
+        }
+    }
+}
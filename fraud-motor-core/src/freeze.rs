@@ -0,0 +1,405 @@
+use crate::error::Result;
+use crate::memory::Memory;
+use crate::value::Value;
+use std::collections::BTreeMap;
+use std::io;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+pub struct Freezer {
+    shared: Arc<Shared>,
+    thread: Option<JoinHandle<()>>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Mode {
+    Always,
+    Below(Value),
+    Above(Value),
+    Increment,
+    Decrement,
+}
+
+#[derive(Clone, Debug)]
+pub struct Freeze {
+    value: Value,
+    mode: Mode,
+    big_endian: bool,
+    paused: bool,
+}
+
+struct Shared {
+    memory: Memory,
+    state: Mutex<State>,
+    condvar: Condvar,
+}
+
+struct State {
+    entries: BTreeMap<usize, Freeze>,
+    interval: Duration,
+    paused: bool,
+    stop: bool,
+}
+
+impl Freezer {
+    pub fn new(memory: Memory, interval: Duration) -> Result<Freezer> {
+        check_interval(interval)?;
+
+        let shared = Arc::new(Shared {
+            memory,
+            state: Mutex::new(State {
+                entries: BTreeMap::new(),
+                interval,
+                paused: false,
+                stop: false,
+            }),
+            condvar: Condvar::new(),
+        });
+        let thread = {
+            let shared = shared.clone();
+            thread::spawn(move || shared.run())
+        };
+
+        Ok(Freezer {
+            shared,
+            thread: Some(thread),
+        })
+    }
+
+    pub fn add(&self, addr: usize, value: Value, mode: Mode, big_endian: bool) -> Result<()> {
+        if let Mode::Below(threshold) | Mode::Above(threshold) = mode {
+            if threshold.ty() != value.ty() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "threshold type does not match the value type",
+                )
+                .into());
+            }
+        }
+
+        let mut freeze = Freeze {
+            value,
+            mode,
+            big_endian,
+            paused: false,
+        };
+
+        freeze.apply(&self.shared.memory, addr)?;
+        self.shared.lock().entries.insert(addr, freeze);
+
+        Ok(())
+    }
+
+    pub fn remove(&self, addr: usize) -> Result<Freeze> {
+        self.shared
+            .lock()
+            .entries
+            .remove(&addr)
+            .ok_or_else(not_found)
+    }
+
+    pub fn get(&self, addr: usize) -> Option<Freeze> {
+        self.shared.lock().entries.get(&addr).cloned()
+    }
+
+    pub fn entries(&self) -> Vec<(usize, Freeze)> {
+        self.shared
+            .lock()
+            .entries
+            .iter()
+            .map(|(&addr, freeze)| (addr, freeze.clone()))
+            .collect()
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        self.shared.lock().paused = paused;
+        self.shared.condvar.notify_all();
+    }
+
+    pub fn paused(&self) -> bool {
+        self.shared.lock().paused
+    }
+
+    pub fn set_entry_paused(&self, addr: usize, paused: bool) -> Result<()> {
+        let mut state = self.shared.lock();
+        let freeze = state.entries.get_mut(&addr).ok_or_else(not_found)?;
+
+        freeze.paused = paused;
+
+        Ok(())
+    }
+
+    pub fn set_interval(&self, interval: Duration) -> Result<()> {
+        check_interval(interval)?;
+
+        self.shared.lock().interval = interval;
+        self.shared.condvar.notify_all();
+
+        Ok(())
+    }
+
+    pub fn interval(&self) -> Duration {
+        self.shared.lock().interval
+    }
+}
+
+impl Drop for Freezer {
+    fn drop(&mut self) {
+        self.shared.lock().stop = true;
+        self.shared.condvar.notify_all();
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap()
+    }
+
+    fn run(&self) {
+        let mut state = self.lock();
+
+        while !state.stop {
+            if !state.paused {
+                let entries: Vec<_> = state
+                    .entries
+                    .iter()
+                    .filter(|(_, freeze)| !freeze.paused)
+                    .map(|(&addr, freeze)| (addr, freeze.value.ty()))
+                    .collect();
+
+                drop(state);
+
+                for (addr, ty) in entries {
+                    let mut buf = vec![0; ty.size()];
+
+                    if self.memory.read(&mut buf, addr).is_err() {
+                        continue;
+                    }
+
+                    // Entries can be removed or paused while reading, so only
+                    // write what is still frozen once the lock is held again.
+                    let mut state = self.lock();
+
+                    if state.stop || state.paused {
+                        break;
+                    }
+
+                    if let Some(entry) = state
+                        .entries
+                        .get_mut(&addr)
+                        .filter(|entry| !entry.paused && entry.value.ty() == ty)
+                    {
+                        if entry.update(entry.decode(&buf)) {
+                            let _ = self.memory.write(&entry.encode(), addr);
+                        }
+                    }
+                }
+
+                state = self.lock();
+
+                if state.stop {
+                    break;
+                }
+            }
+
+            let interval = state.interval;
+
+            state = self.condvar.wait_timeout(state, interval).unwrap().0;
+        }
+    }
+}
+
+impl Freeze {
+    pub fn value(&self) -> Value {
+        self.value
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    pub fn big_endian(&self) -> bool {
+        self.big_endian
+    }
+
+    pub fn paused(&self) -> bool {
+        self.paused
+    }
+
+    fn apply(&mut self, memory: &Memory, addr: usize) -> Result<()> {
+        let mut buf = vec![0; self.value.ty().size()];
+
+        memory.read(&mut buf, addr)?;
+
+        if self.update(self.decode(&buf)) {
+            memory.write(&self.encode(), addr)?;
+        }
+
+        Ok(())
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Value {
+        if self.big_endian {
+            self.value.ty().from_be_bytes(bytes)
+        } else {
+            self.value.ty().from_bytes(bytes)
+        }
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        if self.big_endian {
+            self.value.to_be_bytes()
+        } else {
+            self.value.to_bytes()
+        }
+    }
+
+    fn update(&mut self, current: Value) -> bool {
+        match self.mode {
+            Mode::Always => current != self.value,
+            Mode::Below(threshold) => current < threshold,
+            Mode::Above(threshold) => current > threshold,
+            Mode::Increment if current > self.value => {
+                self.value = current;
+                false
+            }
+            Mode::Decrement if current < self.value => {
+                self.value = current;
+                false
+            }
+            Mode::Increment | Mode::Decrement => current != self.value,
+        }
+    }
+}
+
+fn check_interval(interval: Duration) -> Result<()> {
+    if interval.is_zero() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "interval must be greater than zero",
+        )
+        .into());
+    }
+
+    Ok(())
+}
+
+fn not_found() -> crate::error::Error {
+    io::Error::new(io::ErrorKind::NotFound, "address is not frozen").into()
+}
+
+#[cfg(all(test, target_os = "linux", target_arch = "x86_64"))]
+mod tests {
+    use super::*;
+    use crate::process::{Permissions, Process};
+    use crate::testing::{self, Child};
+
+    #[test]
+    fn test_freezer() {
+        let child = Child::fork(testing::idle);
+        let proc = Process::open(child.id()).unwrap();
+        let open = || {
+            Memory::options()
+                .read(true)
+                .write(true)
+                .open(child.id())
+                .unwrap()
+        };
+        let memory = open();
+        let addr = proc
+            .alloc(64, &Permissions::new(true, true, false))
+            .unwrap();
+        let freezer = Freezer::new(open(), Duration::from_millis(1)).unwrap();
+        let set = |offset, value: u32| memory.write(&value.to_ne_bytes(), addr + offset).unwrap();
+        let get = |offset| {
+            let mut buf = [0; 4];
+            memory.read(&mut buf, addr + offset).unwrap();
+            u32::from_ne_bytes(buf)
+        };
+        let settle = || thread::sleep(Duration::from_millis(50));
+
+        set(4, 100);
+        set(8, 100);
+        set(12, 100);
+        freezer
+            .add(addr, Value::U32(7), Mode::Always, false)
+            .unwrap();
+        freezer
+            .add(addr + 4, Value::U32(50), Mode::Below(Value::U32(20)), false)
+            .unwrap();
+        freezer
+            .add(addr + 8, Value::U32(100), Mode::Increment, false)
+            .unwrap();
+        freezer
+            .add(addr + 12, Value::U32(100), Mode::Decrement, false)
+            .unwrap();
+        assert!(freezer
+            .add(addr + 16, Value::U32(1), Mode::Above(Value::U8(1)), false)
+            .is_err());
+        assert!(freezer.add(0, Value::U32(1), Mode::Always, false).is_err());
+        assert_eq!(get(0), 7);
+
+        set(0, 1);
+        set(4, 30);
+        set(8, 150);
+        set(12, 150);
+        settle();
+        assert_eq!([get(0), get(4), get(8), get(12)], [7, 30, 150, 100]);
+
+        set(4, 10);
+        set(8, 120);
+        set(12, 80);
+        settle();
+        assert_eq!([get(4), get(8), get(12)], [50, 150, 80]);
+        assert_eq!(freezer.get(addr + 12).unwrap().value(), Value::U32(80));
+
+        freezer.set_paused(true);
+        settle();
+        set(0, 1);
+        settle();
+        assert_eq!(get(0), 1);
+
+        freezer.set_entry_paused(addr, true).unwrap();
+        freezer.set_paused(false);
+        settle();
+        assert_eq!(get(0), 1);
+
+        freezer.set_entry_paused(addr, false).unwrap();
+        settle();
+        assert_eq!(get(0), 7);
+
+        assert!(freezer.set_interval(Duration::ZERO).is_err());
+        assert!(Freezer::new(open(), Duration::ZERO).is_err());
+        assert_eq!(freezer.interval(), Duration::from_millis(1));
+
+        set(20, 100u32.to_be());
+        freezer
+            .add(
+                addr + 20,
+                Value::U32(250),
+                Mode::Below(Value::U32(200)),
+                true,
+            )
+            .unwrap();
+        assert_eq!(get(20), 250u32.to_be());
+        set(20, 300u32.to_be());
+        settle();
+        assert_eq!(get(20), 300u32.to_be());
+        set(20, 150u32.to_be());
+        settle();
+        assert_eq!(get(20), 250u32.to_be());
+        assert!(freezer.get(addr + 20).unwrap().big_endian());
+
+        freezer.remove(addr).unwrap();
+        assert!(freezer.remove(addr).is_err());
+        set(0, 1);
+        settle();
+        assert_eq!(get(0), 1);
+        assert_eq!(freezer.entries().len(), 4);
+    }
+}
@@ -0,0 +1,354 @@
+use crate::error::{Error, Result};
+use crate::memory::{page_size, Memory};
+use crate::process::{Permissions, Process};
+use iced_x86::{
+    BlockEncoder, BlockEncoderOptions, Code, Decoder, DecoderOptions, Instruction,
+    InstructionBlock, Mnemonic, OpKind,
+};
+use std::io;
+use std::ops::Range;
+use std::time::Duration;
+use std::{slice, thread};
+
+const JMP_SIZE: usize = 5;
+const RELAY_SIZE: usize = 16;
+const MAX_PROLOGUE_SIZE: usize = 64;
+const MAX_DISTANCE: usize = 0x7fff_0000;
+const MIN_ADDR: usize = 0x10000;
+const MAX_ADDR: usize = 0x7fff_ffff_0000;
+const NOP: u8 = 0x90;
+const BUSY_RETRIES: usize = 100;
+
+#[derive(Clone, Debug)]
+pub struct Hook {
+    target: usize,
+    detour: usize,
+    page: usize,
+    original: Vec<u8>,
+}
+
+impl Hook {
+    pub fn install(
+        memory: &Memory,
+        process: &Process,
+        target: usize,
+        detour: usize,
+    ) -> Result<Hook> {
+        let page_size = page_size();
+        let mut code = vec![0; MAX_PROLOGUE_SIZE];
+        let len = match memory.read_partial(&mut code, target)?.first() {
+            Some(range) if range.start == target => range.len(),
+            _ => return Err(Error::Unmapped(target)),
+        };
+        let prologue = decode_prologue(&code[..len], target)?;
+        let size: usize = prologue.iter().map(Instruction::len).sum();
+        let page = alloc_near(process, target)?;
+
+        let result = (|| {
+            let mut block = prologue.clone();
+
+            block.push(
+                Instruction::with_branch(Code::Jmp_rel32_64, (target + size) as u64)
+                    .map_err(|err| invalid(&err.to_string()))?,
+            );
+
+            let relocated = BlockEncoder::encode(
+                64,
+                InstructionBlock::new(&block, (page + RELAY_SIZE) as u64),
+                BlockEncoderOptions::NONE,
+            )
+            .map_err(|err| invalid(&err.to_string()))?;
+            let mut trampoline = vec![0xff, 0x25, 0, 0, 0, 0];
+
+            trampoline.extend_from_slice(&(detour as u64).to_le_bytes());
+            trampoline.resize(RELAY_SIZE, NOP);
+            trampoline.extend_from_slice(&relocated.code_buffer);
+
+            if trampoline.len() > page_size {
+                return Err(invalid("relocated prologue does not fit the trampoline"));
+            }
+
+            memory.write(&trampoline, page)?;
+            process.protect(page..page + page_size, &Permissions::new(true, false, true))?;
+
+            let mut jump = vec![0xe9];
+
+            jump.extend_from_slice(&rel32(target + JMP_SIZE, page)?.to_le_bytes());
+            jump.resize(size, NOP);
+            let busy = target..target + size;
+
+            write_stopped(process, &jump, target, slice::from_ref(&busy))
+        })();
+
+        if let Err(err) = result {
+            let _ = process.free(page, page_size);
+            return Err(err);
+        }
+
+        Ok(Hook {
+            target,
+            detour,
+            page,
+            original: code[..size].to_owned(),
+        })
+    }
+
+    pub fn uninstall(self, process: &Process) -> Result<()> {
+        let page_size = page_size();
+        let busy = [
+            self.target..self.target + self.original.len(),
+            self.page..self.page + page_size,
+        ];
+
+        write_stopped(process, &self.original, self.target, &busy)?;
+        process.free(self.page, page_size)
+    }
+
+    pub fn target(&self) -> usize {
+        self.target
+    }
+
+    pub fn detour(&self) -> usize {
+        self.detour
+    }
+
+    pub fn trampoline(&self) -> usize {
+        self.page + RELAY_SIZE
+    }
+
+    pub fn original(&self) -> &[u8] {
+        &self.original
+    }
+}
+
+fn decode_prologue(code: &[u8], addr: usize) -> Result<Vec<Instruction>> {
+    let mut decoder = Decoder::with_ip(64, code, addr as u64, DecoderOptions::NONE);
+    let mut prologue = Vec::new();
+    let mut size = 0;
+
+    while size < JMP_SIZE {
+        let instruction = decoder.decode();
+
+        if instruction.is_invalid() {
+            return Err(invalid("cannot decode function prologue"));
+        }
+
+        size += instruction.len();
+
+        if size < JMP_SIZE
+            && matches!(
+                instruction.mnemonic(),
+                Mnemonic::Ret | Mnemonic::Retf | Mnemonic::Jmp | Mnemonic::Int3 | Mnemonic::Ud2
+            )
+        {
+            return Err(invalid("function is too short to hook"));
+        }
+
+        prologue.push(instruction);
+    }
+
+    let range = addr..addr + size;
+
+    if prologue.iter().any(|instruction| {
+        instruction.op_kinds().any(|kind| {
+            matches!(
+                kind,
+                OpKind::NearBranch16 | OpKind::NearBranch32 | OpKind::NearBranch64
+            )
+        }) && range.contains(&(instruction.near_branch_target() as usize))
+    }) {
+        return Err(invalid("function prologue branches into itself"));
+    }
+
+    Ok(prologue)
+}
+
+fn write_stopped(process: &Process, buf: &[u8], addr: usize, busy: &[Range<usize>]) -> Result<()> {
+    for _ in 1..BUSY_RETRIES {
+        match process.write_stopped(buf, addr, busy) {
+            Err(Error::Io(err)) if err.kind() == io::ErrorKind::ResourceBusy => {
+                thread::sleep(Duration::from_millis(1));
+            }
+            result => return result,
+        }
+    }
+
+    process.write_stopped(buf, addr, busy)
+}
+
+fn alloc_near(process: &Process, target: usize) -> Result<usize> {
+    let mut candidates = Vec::new();
+    let mut prev = MIN_ADDR;
+
+    for region in process.regions()? {
+        let region = region?;
+
+        gap_candidates(&mut candidates, prev, region.start().min(MAX_ADDR), target);
+        prev = prev.max(region.end());
+    }
+
+    gap_candidates(&mut candidates, prev, MAX_ADDR, target);
+    candidates.sort_by_key(|addr| addr.abs_diff(target));
+
+    for addr in candidates {
+        if addr.abs_diff(target) > MAX_DISTANCE {
+            break;
+        }
+
+        if let Ok(page) = process.alloc_at(addr, page_size(), &Permissions::new(true, true, false))
+        {
+            return Ok(page);
+        }
+    }
+
+    Err(io::Error::new(
+        io::ErrorKind::OutOfMemory,
+        "no free memory within range of the hook target",
+    )
+    .into())
+}
+
+fn gap_candidates(candidates: &mut Vec<usize>, start: usize, end: usize, target: usize) {
+    let page_size = page_size();
+    let start = start.div_ceil(page_size) * page_size;
+    let end = end / page_size * page_size;
+
+    if start >= end {
+        return;
+    }
+
+    if target < start {
+        candidates.push(start);
+    } else if target >= end {
+        candidates.push(end - page_size);
+    } else {
+        candidates.push(target / page_size * page_size);
+    }
+}
+
+fn rel32(from: usize, to: usize) -> Result<i32> {
+    i32::try_from(to.wrapping_sub(from) as isize)
+        .map_err(|_| invalid("jump target is out of range"))
+}
+
+fn invalid(msg: &str) -> Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg).into()
+}
+
+#[cfg(all(test, target_os = "linux", target_arch = "x86_64"))]
+mod tests {
+    use super::*;
+    use crate::asm;
+    use crate::testing::{self, Child};
+    use std::hint;
+
+    #[inline(never)]
+    extern "C" fn hook_target(i: u64) -> u64 {
+        hint::black_box(i) * 3 + 1
+    }
+
+    extern "C" fn hook_detour(i: u64) -> u64 {
+        i + 100
+    }
+
+    fn hook_target_caller() -> ! {
+        let mut i = 0;
+
+        loop {
+            hook_target(hint::black_box(i));
+            unsafe { libc::usleep(100) };
+            i += 1;
+        }
+    }
+
+    #[test]
+    fn test_hook() {
+        let child = Child::fork(testing::idle);
+        let proc = Process::open(child.id()).unwrap();
+        let memory = Memory::options()
+            .read(true)
+            .write(true)
+            .open(child.id())
+            .unwrap();
+        let rwx = Permissions::new(true, true, true);
+        let addr = proc.alloc(page_size() * 2, &rwx).unwrap();
+        let target = addr + 0x100;
+        let detour = addr + 0x200;
+        let code = asm::assemble("mov rax, [rip+0x1000]; add rax, rdi; ret", target).unwrap();
+        let data = target + 7 + 0x1000;
+
+        memory.write(&code, target).unwrap();
+        memory.write(&1000u64.to_le_bytes(), data).unwrap();
+        memory
+            .write(
+                &asm::assemble("lea rax, [rdi*2+7]; ret", detour).unwrap(),
+                detour,
+            )
+            .unwrap();
+        memory.write(&[0xc3], addr).unwrap();
+
+        assert_eq!(proc.call(target, &[5]).unwrap(), 1005);
+        assert!(Hook::install(&memory, &proc, addr, detour).is_err());
+
+        let hook = Hook::install(&memory, &proc, target, detour).unwrap();
+
+        assert_eq!(hook.original(), &code[..7]);
+        assert!(hook.trampoline().abs_diff(target) <= MAX_DISTANCE);
+        assert_eq!(proc.call(target, &[5]).unwrap(), 17);
+        assert_eq!(proc.call(hook.trampoline(), &[5]).unwrap(), 1005);
+
+        let trampoline = hook.trampoline();
+
+        hook.uninstall(&proc).unwrap();
+
+        let mut buf = vec![0; code.len()];
+
+        memory.read(&mut buf, target).unwrap();
+        assert_eq!(buf, code);
+        assert_eq!(proc.call(target, &[5]).unwrap(), 1005);
+        assert!(proc
+            .regions()
+            .unwrap()
+            .map(Result::unwrap)
+            .all(|region| !(region.start()..region.end()).contains(&trampoline)));
+    }
+
+    #[test]
+    fn test_hook_compiled() {
+        let child = Child::fork(hook_target_caller);
+        let proc = Process::open(child.id()).unwrap();
+        let memory = Memory::options()
+            .read(true)
+            .write(true)
+            .open(child.id())
+            .unwrap();
+        let target = hook_target as extern "C" fn(u64) -> u64 as usize;
+        let detour = hook_detour as extern "C" fn(u64) -> u64 as usize;
+        let mut code = [0; JMP_SIZE];
+
+        memory.read(&mut code, target).unwrap();
+        assert_eq!(proc.call(target, &[5]).unwrap(), 16);
+
+        for _ in 0..10 {
+            let hook = Hook::install(&memory, &proc, target, detour).unwrap();
+
+            assert_eq!(&hook.original()[..JMP_SIZE], code);
+            assert_eq!(proc.call(target, &[5]).unwrap(), 105);
+            assert_eq!(proc.call(hook.trampoline(), &[5]).unwrap(), 16);
+
+            hook.uninstall(&proc).unwrap();
+            assert_eq!(proc.call(target, &[5]).unwrap(), 16);
+        }
+
+        assert_ne!(proc.info().unwrap().state(), crate::process::State::Zombie);
+    }
+
+    #[test]
+    fn test_decode_prologue_branch() {
+        let addr = 0x1000;
+
+        assert!(decode_prologue(&[0x85, 0xff, 0x74, 0xfc, 0xc3], addr).is_err());
+        assert!(decode_prologue(&[0x85, 0xff, 0x74, 0x3c, 0xc3], addr).is_ok());
+        assert!(decode_prologue(&[0x85, 0xff, 0xe3, 0xfc, 0xc3], addr).is_err());
+    }
+}
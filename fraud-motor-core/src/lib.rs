@@ -1,6 +1,20 @@
 mod sys;
+#[cfg(all(test, target_os = "linux"))]
+mod testing;
 
+pub mod asm;
+pub mod debug;
+pub mod disasm;
 pub mod dump;
+pub mod elf;
+pub mod error;
+pub mod freeze;
+pub mod hook;
 pub mod memory;
+pub mod patch;
+pub mod pointer;
 pub mod process;
 pub mod scan;
+pub mod symbol;
+pub mod thread;
+pub mod value;
@@ -1,24 +1,81 @@
+use crate::error::{Error, Result};
 use crate::sys::memory as memory_imp;
-use std::io;
+use std::ops::Range;
 
 pub struct Memory(memory_imp::Memory);
 
 pub struct Options(memory_imp::Options);
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Backend {
+    ProcMem,
+    ProcessVm,
+}
+
 impl Memory {
     pub fn options() -> Options {
         Options(memory_imp::Options::new())
     }
 
-    pub fn read(&self, buf: &mut [u8], addr: usize) -> io::Result<()> {
+    pub fn read(&self, buf: &mut [u8], addr: usize) -> Result<()> {
         self.0.read(buf, addr)
     }
 
-    pub fn write(&self, buf: &[u8], addr: usize) -> io::Result<()> {
+    pub fn read_partial(&self, buf: &mut [u8], addr: usize) -> Result<Vec<Range<usize>>> {
+        let page_size = page_size();
+        let mut ranges: Vec<Range<usize>> = Vec::new();
+        let mut bulk = true;
+        let mut pos = 0;
+
+        while pos < buf.len() {
+            let end = if bulk {
+                buf.len()
+            } else {
+                ((addr + pos) / page_size + 1) * page_size - addr
+            }
+            .min(buf.len());
+
+            let read = match self.0.read(&mut buf[pos..end], addr + pos) {
+                Ok(()) => end - pos,
+                Err(Error::PartialRead { read, .. }) => read,
+                Err(Error::Unmapped(_)) => 0,
+                Err(err) => return Err(err),
+            };
+
+            if read > 0 {
+                match ranges.last_mut() {
+                    Some(range) if range.end == addr + pos => range.end += read,
+                    _ => ranges.push(addr + pos..addr + pos + read),
+                }
+            }
+
+            pos += read;
+            bulk = pos == end;
+
+            if !bulk {
+                let next = (((addr + pos) / page_size + 1) * page_size - addr).min(buf.len());
+
+                buf[pos..next].fill(0);
+                pos = next;
+            }
+        }
+
+        Ok(ranges)
+    }
+
+    pub fn read_many(&self, reads: &mut [(usize, &mut [u8])]) -> Vec<Result<()>> {
+        self.0.read_many(reads)
+    }
+
+    pub fn write(&self, buf: &[u8], addr: usize) -> Result<()> {
         self.0.write(buf, addr)
     }
 }
 
+pub fn page_size() -> usize {
+    memory_imp::page_size()
+}
+
 impl Options {
     pub fn read(&mut self, read: bool) -> &mut Options {
         self.0.read(read);
@@ -30,7 +87,12 @@ impl Options {
         self
     }
 
-    pub fn open(&self, id: u32) -> io::Result<Memory> {
+    pub fn backend(&mut self, backend: Backend) -> &mut Options {
+        self.0.backend(backend);
+        self
+    }
+
+    pub fn open(&self, id: u32) -> Result<Memory> {
         memory_imp::Memory::open(id, &self.0).map(Memory)
     }
 }
@@ -38,6 +100,7 @@ impl Options {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::error::Error;
     use std::{process, ptr};
 
     #[test]
@@ -48,10 +111,146 @@ mod tests {
         let addr = ptr::addr_of!(secret) as usize;
 
         memory.read(&mut buf, addr).unwrap();
-        assert!(memory.read(&mut buf, 0).is_err());
+        assert!(matches!(memory.read(&mut buf, 0), Err(Error::Unmapped(0))));
         assert_eq!(secret, buf);
     }
 
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_memory_partial_read() {
+        let memory = Memory::options().read(true).open(process::id()).unwrap();
+        let mut buf = [0; 8];
+
+        let addr = unsafe {
+            let addr = libc::mmap(
+                ptr::null_mut(),
+                8192,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            ) as usize;
+
+            libc::munmap((addr + 4096) as *mut libc::c_void, 4096);
+            addr
+        };
+
+        let result = memory.read(&mut buf, addr + 4092);
+        assert!(matches!(result, Err(Error::PartialRead { read: 4, .. })));
+
+        unsafe { libc::munmap(addr as *mut libc::c_void, 4096) };
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_memory_read_partial() {
+        let page_size = page_size();
+        let memory = Memory::options().read(true).open(process::id()).unwrap();
+        let mut buf = vec![1; page_size * 3 - 16];
+
+        let addr = unsafe {
+            let addr = libc::mmap(
+                ptr::null_mut(),
+                page_size * 3,
+                libc::PROT_READ | libc::PROT_WRITE,
+                libc::MAP_PRIVATE | libc::MAP_ANONYMOUS,
+                -1,
+                0,
+            ) as usize;
+
+            libc::munmap((addr + page_size) as *mut libc::c_void, page_size);
+            *((addr + page_size * 2) as *mut u8) = 42;
+            addr
+        };
+
+        let ranges = memory.read_partial(&mut buf, addr + 16).unwrap();
+
+        assert_eq!(
+            ranges,
+            [
+                addr + 16..addr + page_size,
+                addr + page_size * 2..addr + page_size * 3
+            ]
+        );
+        assert!(buf.iter().take(page_size * 2 - 16).all(|&byte| byte == 0));
+        assert_eq!(buf[page_size * 2 - 16], 42);
+        assert!(buf.iter().skip(page_size * 2 - 15).all(|&byte| byte == 0));
+
+        unsafe {
+            libc::munmap(addr as *mut libc::c_void, page_size);
+            libc::munmap((addr + page_size * 2) as *mut libc::c_void, page_size);
+        }
+    }
+
+    #[test]
+    fn test_memory_read_many() {
+        let secrets: Vec<_> = (0..2000u32).map(|i| Box::new(i * 7)).collect();
+        let mut bufs = vec![[0; 4]; secrets.len() + 1];
+
+        for backend in [Backend::ProcMem, Backend::ProcessVm] {
+            let memory = Memory::options()
+                .read(true)
+                .backend(backend)
+                .open(process::id())
+                .unwrap();
+
+            let addrs = secrets
+                .iter()
+                .map(|secret| ptr::addr_of!(**secret) as usize)
+                .chain([0]);
+
+            let mut reads: Vec<_> = addrs.zip(bufs.iter_mut().map(|buf| &mut buf[..])).collect();
+            reads.swap(0, secrets.len());
+
+            let results = memory.read_many(&mut reads);
+
+            assert_eq!(results.len(), reads.len());
+            assert!(matches!(results[0], Err(Error::Unmapped(0))));
+            assert!(results[1..].iter().all(Result::is_ok));
+
+            for (secret, buf) in secrets.iter().zip(&bufs) {
+                assert_eq!(u32::from_ne_bytes(*buf), **secret);
+            }
+        }
+    }
+
+    #[test]
+    fn test_memory_process_vm() {
+        let memory = Memory::options()
+            .read(true)
+            .write(true)
+            .backend(Backend::ProcessVm)
+            .open(process::id())
+            .unwrap();
+
+        let mut buf = u32::to_ne_bytes(0);
+        let secret = Box::new(u32::to_ne_bytes(1337));
+        let addr = ptr::addr_of!(*secret) as usize;
+
+        memory.read(&mut buf, addr).unwrap();
+        assert_eq!(buf, *secret);
+        assert!(matches!(memory.read(&mut buf, 0), Err(Error::Unmapped(0))));
+
+        memory.write(&u32::to_ne_bytes(42), addr).unwrap();
+        assert_eq!(*secret, u32::to_ne_bytes(42));
+
+        let open = |read, write| {
+            Memory::options()
+                .read(read)
+                .write(write)
+                .backend(Backend::ProcessVm)
+                .open(process::id())
+        };
+        let reader = open(true, false).unwrap();
+        let writer = open(false, true).unwrap();
+
+        assert!(reader.write(&buf, addr).is_err());
+        assert!(writer.read(&mut buf, addr).is_err());
+        assert!(writer.read_many(&mut [(addr, &mut buf[..])])[0].is_err());
+        assert!(open(false, false).is_err());
+        assert_eq!(*secret, u32::to_ne_bytes(42));
+    }
+
     #[test]
     fn test_memory_write() {
         let memory = Memory::options().write(true).open(process::id()).unwrap();
@@ -0,0 +1,318 @@
+use crate::error::{Error, Result};
+use crate::memory::Memory;
+use crate::process::{Permissions, Process, Region};
+use std::collections::BTreeMap;
+use std::io;
+
+const NOP: u8 = 0x90;
+
+pub struct Patches {
+    memory: Memory,
+    process: Process,
+    patches: BTreeMap<String, Patch>,
+}
+
+#[derive(Clone, Debug)]
+pub struct Patch {
+    addr: usize,
+    original: Vec<u8>,
+    bytes: Vec<u8>,
+    enabled: bool,
+}
+
+impl Patches {
+    pub fn new(memory: Memory, process: Process) -> Patches {
+        Patches {
+            memory,
+            process,
+            patches: BTreeMap::new(),
+        }
+    }
+
+    pub fn add(
+        &mut self,
+        name: &str,
+        addr: usize,
+        bytes: &[u8],
+        expected: Option<&[u8]>,
+    ) -> Result<()> {
+        if self.patches.contains_key(name) {
+            return Err(
+                io::Error::new(io::ErrorKind::AlreadyExists, "patch already exists").into(),
+            );
+        }
+
+        if bytes.is_empty() || expected.is_some_and(|expected| expected.len() != bytes.len()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "patch bytes must be non-empty and match the expected length",
+            )
+            .into());
+        }
+
+        let end = addr + bytes.len();
+
+        if self
+            .patches
+            .values()
+            .any(|patch| addr < patch.end() && patch.addr < end)
+        {
+            return Err(io::Error::new(
+                io::ErrorKind::AlreadyExists,
+                "patch overlaps an existing patch",
+            )
+            .into());
+        }
+
+        let mut original = vec![0; bytes.len()];
+
+        self.memory.read(&mut original, addr)?;
+
+        if expected.is_some_and(|expected| expected != original) {
+            return Err(mismatch());
+        }
+
+        self.patches.insert(
+            name.to_owned(),
+            Patch {
+                addr,
+                original,
+                bytes: bytes.to_owned(),
+                enabled: false,
+            },
+        );
+
+        Ok(())
+    }
+
+    pub fn add_nop(
+        &mut self,
+        name: &str,
+        addr: usize,
+        len: usize,
+        expected: Option<&[u8]>,
+    ) -> Result<()> {
+        self.add(name, addr, &vec![NOP; len], expected)
+    }
+
+    pub fn remove(&mut self, name: &str) -> Result<()> {
+        self.disable(name)?;
+        self.patches.remove(name);
+
+        Ok(())
+    }
+
+    pub fn enable(&mut self, name: &str) -> Result<()> {
+        let patch = self.get(name)?;
+
+        if patch.enabled {
+            return Ok(());
+        }
+
+        let mut current = vec![0; patch.original.len()];
+
+        self.memory.read(&mut current, patch.addr)?;
+
+        if current != patch.original {
+            return Err(mismatch());
+        }
+
+        self.write(&patch.bytes, patch.addr)?;
+        self.patches.get_mut(name).unwrap().enabled = true;
+
+        Ok(())
+    }
+
+    pub fn disable(&mut self, name: &str) -> Result<()> {
+        let patch = self.get(name)?;
+
+        if !patch.enabled {
+            return Ok(());
+        }
+
+        self.write(&patch.original, patch.addr)?;
+        self.patches.get_mut(name).unwrap().enabled = false;
+
+        Ok(())
+    }
+
+    pub fn toggle(&mut self, name: &str) -> Result<bool> {
+        if self.get(name)?.enabled {
+            self.disable(name).map(|_| false)
+        } else {
+            self.enable(name).map(|_| true)
+        }
+    }
+
+    pub fn restore(&mut self) -> Result<()> {
+        let names: Vec<_> = self.patches.keys().cloned().collect();
+        let mut result = Ok(());
+
+        for name in names {
+            if let Err(err) = self.disable(&name) {
+                result = result.and(Err(err));
+            }
+        }
+
+        result
+    }
+
+    pub fn get(&self, name: &str) -> Result<&Patch> {
+        self.patches
+            .get(name)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "patch not found").into())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Patch)> {
+        self.patches
+            .iter()
+            .map(|(name, patch)| (name.as_str(), patch))
+    }
+
+    fn write(&self, buf: &[u8], addr: usize) -> Result<()> {
+        if self.memory.write(buf, addr).is_ok() {
+            return Ok(());
+        }
+
+        let end = addr + buf.len();
+        let regions: Vec<_> = self
+            .process
+            .regions()?
+            .filter(|region| {
+                region
+                    .as_ref()
+                    .map_or(true, |region| region.start() < end && addr < region.end())
+            })
+            .collect::<Result<_>>()?;
+
+        if regions.first().is_none_or(|region| region.start() > addr)
+            || regions
+                .windows(2)
+                .any(|pair| pair[0].end() != pair[1].start())
+            || regions.last().is_none_or(|region| region.end() < end)
+        {
+            return Err(Error::Unmapped(addr));
+        }
+
+        let range = |region: &Region| region.start().max(addr)..region.end().min(end);
+
+        for region in &regions {
+            let perms = region.permissions();
+            let writable = Permissions::new(perms.read(), true, perms.exec());
+
+            self.process.protect(range(region), &writable)?;
+        }
+
+        let result = self.memory.write(buf, addr);
+
+        for region in &regions {
+            self.process.protect(range(region), &region.permissions())?;
+        }
+
+        result
+    }
+}
+
+impl Drop for Patches {
+    fn drop(&mut self) {
+        let _ = self.restore();
+    }
+}
+
+impl Patch {
+    pub fn addr(&self) -> usize {
+        self.addr
+    }
+
+    pub fn end(&self) -> usize {
+        self.addr + self.bytes.len()
+    }
+
+    pub fn original(&self) -> &[u8] {
+        &self.original
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+}
+
+fn mismatch() -> Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        "memory does not match the expected original bytes",
+    )
+    .into()
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use crate::memory::Backend;
+    use crate::testing::{self, Child};
+    use std::hint;
+
+    #[inline(never)]
+    fn patch_target(i: u64) -> u64 {
+        hint::black_box(i) * 3 + 1
+    }
+
+    #[test]
+    fn test_patches() {
+        let child = Child::fork(testing::idle);
+        let proc = Process::open(child.id()).unwrap();
+        let memory = Memory::options()
+            .read(true)
+            .write(true)
+            .backend(Backend::ProcessVm)
+            .open(child.id())
+            .unwrap();
+        let reader = Memory::options().read(true).open(child.id()).unwrap();
+        let addr = patch_target as fn(u64) -> u64 as usize;
+        let read = |len| {
+            let mut buf = vec![0; len];
+            reader.read(&mut buf, addr).unwrap();
+            buf
+        };
+
+        let original = read(8);
+        let mut patches = Patches::new(memory, Process::open(child.id()).unwrap());
+
+        assert!(patches.add_nop("nop", addr, 4, Some(&[0; 4])).is_err());
+        patches
+            .add_nop("nop", addr, 4, Some(&original[..4]))
+            .unwrap();
+        patches.add("ret", addr + 4, &[0xc3], None).unwrap();
+        assert!(patches.add("nop", addr + 8, &[0xc3], None).is_err());
+        assert!(patches.add("overlap", addr + 2, &[0xc3; 4], None).is_err());
+
+        patches.enable("nop").unwrap();
+        assert_eq!(read(8)[..4], [NOP; 4]);
+        assert!(patches.get("nop").unwrap().enabled());
+
+        let region = proc
+            .regions()
+            .unwrap()
+            .map(Result::unwrap)
+            .find(|region| region.start() <= addr && addr < region.end())
+            .unwrap();
+        assert!(!region.permissions().write());
+
+        assert!(patches.toggle("ret").unwrap());
+        assert_eq!(read(8)[..5], [NOP, NOP, NOP, NOP, 0xc3]);
+        assert!(!patches.toggle("nop").unwrap());
+        assert_eq!(read(8)[..5], [&original[..4], &[0xc3]].concat());
+
+        patches.remove("ret").unwrap();
+        assert_eq!(read(8), original);
+        assert!(patches.enable("ret").is_err());
+
+        patches.enable("nop").unwrap();
+        drop(patches);
+        assert_eq!(read(8), original);
+    }
+}
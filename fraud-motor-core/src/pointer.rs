@@ -0,0 +1,463 @@
+use crate::dump::ProcessDump;
+use crate::elf::Elf;
+use crate::error::Result;
+use crate::memory::Memory;
+use crate::process::{Process, Region, RegionKind};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::fmt::{self, Display, Formatter};
+use std::ops::{Range, RangeInclusive};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::{mem, thread};
+
+const POINTER_SIZE: usize = mem::size_of::<usize>();
+const CHUNK_SIZE: usize = 1 << 24;
+
+pub struct PointerMap {
+    pointers: Vec<(usize, usize)>,
+    roots: Vec<Root>,
+    threads: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Root {
+    name: String,
+    base: usize,
+    range: Range<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct PointerChain {
+    root: String,
+    base: usize,
+    offset: isize,
+    offsets: Vec<usize>,
+}
+
+struct Search<'a> {
+    map: &'a PointerMap,
+    depth: usize,
+    max_offset: usize,
+    limit: usize,
+    found: &'a AtomicUsize,
+}
+
+impl PointerMap {
+    pub fn new(dump: &ProcessDump, mut roots: Vec<Root>) -> PointerMap {
+        let threads = thread::available_parallelism().map_or(1, |n| n.get());
+        let valid: Vec<Range<usize>> = dump
+            .regions()
+            .iter()
+            .filter_map(|(region, data)| Some((region, data.as_ref().ok()?)))
+            .flat_map(|(region, data)| {
+                data.readable()
+                    .iter()
+                    .map(|range| region.start() + range.start..region.start() + range.end)
+            })
+            .collect();
+        let chunks: Vec<(usize, &[u8])> = dump
+            .regions()
+            .iter()
+            .filter_map(|(region, data)| Some((region, data.as_ref().ok()?)))
+            .flat_map(|(region, data)| {
+                data.readable().iter().flat_map(move |range| {
+                    let start = range.start.next_multiple_of(POINTER_SIZE);
+
+                    (start..range.end.max(start))
+                        .step_by(CHUNK_SIZE)
+                        .map(move |offset| {
+                            let end = (offset + CHUNK_SIZE).min(range.end);
+
+                            (region.start() + offset, &data.data()[offset..end])
+                        })
+                })
+            })
+            .collect();
+        let next = AtomicUsize::new(0);
+        let is_valid = |value: usize| {
+            let i = valid.partition_point(|range| range.end <= value);
+
+            valid.get(i).is_some_and(|range| range.start <= value)
+        };
+
+        let parts: Vec<Vec<(usize, usize)>> = thread::scope(|scope| {
+            let workers: Vec<_> = (0..threads)
+                .map(|_| {
+                    scope.spawn(|| {
+                        let mut pointers = Vec::new();
+
+                        while let Some(&(addr, data)) =
+                            chunks.get(next.fetch_add(1, Ordering::Relaxed))
+                        {
+                            for (i, bytes) in data.chunks_exact(POINTER_SIZE).enumerate() {
+                                let value = usize::from_ne_bytes(bytes.try_into().unwrap());
+
+                                if is_valid(value) {
+                                    pointers.push((value, addr + i * POINTER_SIZE));
+                                }
+                            }
+                        }
+
+                        pointers.sort_unstable();
+                        pointers
+                    })
+                })
+                .collect();
+
+            workers
+                .into_iter()
+                .map(|worker| worker.join().unwrap())
+                .collect()
+        });
+
+        roots.sort_by_key(|root| root.range.start);
+
+        PointerMap {
+            pointers: merge(parts),
+            roots,
+            threads,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.pointers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pointers.is_empty()
+    }
+
+    pub fn roots(&self) -> &[Root] {
+        &self.roots
+    }
+
+    pub fn pointers_to(&self, range: RangeInclusive<usize>) -> &[(usize, usize)] {
+        let start = self
+            .pointers
+            .partition_point(|&(value, _)| value < *range.start());
+        let end = self
+            .pointers
+            .partition_point(|&(value, _)| value <= *range.end());
+
+        &self.pointers[start..end]
+    }
+
+    pub fn find(
+        &self,
+        target: usize,
+        depth: usize,
+        max_offset: usize,
+        limit: usize,
+    ) -> Vec<PointerChain> {
+        let found = AtomicUsize::new(0);
+        let first = self.pointers_to(target.saturating_sub(max_offset)..=target);
+        let chains = Mutex::new(Vec::new());
+
+        for depth in 1..=depth {
+            let search = Search {
+                map: self,
+                depth,
+                max_offset,
+                limit,
+                found: &found,
+            };
+            let next = AtomicUsize::new(0);
+
+            thread::scope(|scope| {
+                for _ in 0..self.threads {
+                    scope.spawn(|| {
+                        let mut local = Vec::new();
+                        let mut offsets = Vec::new();
+
+                        while let Some(&(value, addr)) =
+                            first.get(next.fetch_add(1, Ordering::Relaxed))
+                        {
+                            offsets.push(target - value);
+                            search.visit(addr, 1, &mut offsets, &mut local);
+                            offsets.pop();
+                        }
+
+                        chains.lock().unwrap().append(&mut local);
+                    });
+                }
+            });
+
+            if found.load(Ordering::Relaxed) >= limit {
+                break;
+            }
+        }
+
+        let mut chains = chains.into_inner().unwrap();
+
+        chains.sort_by(|a, b| (a.offsets.len(), a).cmp(&(b.offsets.len(), b)));
+        chains.truncate(limit);
+        chains
+    }
+
+    fn root(&self, addr: usize) -> Option<&Root> {
+        let i = self.roots.partition_point(|root| root.range.end <= addr);
+
+        self.roots.get(i).filter(|root| root.range.start <= addr)
+    }
+}
+
+impl<'a> Search<'a> {
+    fn visit(
+        &self,
+        addr: usize,
+        level: usize,
+        offsets: &mut Vec<usize>,
+        chains: &mut Vec<PointerChain>,
+    ) {
+        if self.found.load(Ordering::Relaxed) >= self.limit {
+            return;
+        }
+
+        if let Some(root) = self.map.root(addr) {
+            if level == self.depth {
+                self.found.fetch_add(1, Ordering::Relaxed);
+                chains.push(PointerChain {
+                    root: root.name.clone(),
+                    base: root.base,
+                    offset: addr.wrapping_sub(root.base) as isize,
+                    offsets: offsets.iter().rev().copied().collect(),
+                });
+            }
+
+            return;
+        }
+
+        if level >= self.depth {
+            return;
+        }
+
+        for &(value, next) in self
+            .map
+            .pointers_to(addr.saturating_sub(self.max_offset)..=addr)
+        {
+            offsets.push(addr - value);
+            self.visit(next, level + 1, offsets, chains);
+            offsets.pop();
+        }
+    }
+}
+
+impl Root {
+    pub fn new(name: &str, base: usize, range: Range<usize>) -> Root {
+        Root {
+            name: name.to_owned(),
+            base,
+            range,
+        }
+    }
+
+    pub fn modules(process: &Process) -> Result<Vec<Root>> {
+        let regions: Vec<Region> = process.regions()?.collect::<Result<_>>()?;
+        let mut roots = Vec::new();
+
+        for module in process.modules()? {
+            let module = module?;
+
+            for segment in module.segments() {
+                if segment.permissions().write() {
+                    roots.push(Root::new(
+                        module.name(),
+                        module.base(),
+                        segment.start()..segment.end(),
+                    ));
+                }
+            }
+
+            let Some(bss) = regions.iter().find(|region| {
+                region.start() == module.end()
+                    && region.kind() == RegionKind::Anonymous
+                    && region.permissions().write()
+            }) else {
+                continue;
+            };
+
+            if let Ok(elf) = Elf::open(module.path()) {
+                let end = module.base() + (elf.end() - elf.base()) as usize;
+
+                if end > bss.start() {
+                    roots.push(Root::new(
+                        module.name(),
+                        module.base(),
+                        bss.start()..end.min(bss.end()),
+                    ));
+                }
+            }
+        }
+
+        Ok(roots)
+    }
+
+    pub fn stacks(process: &Process) -> Result<Vec<Root>> {
+        let mut roots = Vec::new();
+
+        for thread in process.threads()? {
+            if let Some(stack) = thread?.stack() {
+                roots.push(Root::new(
+                    &format!("stack{}", roots.len()),
+                    stack.end(),
+                    stack.start()..stack.end(),
+                ));
+            }
+        }
+
+        Ok(roots)
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn base(&self) -> usize {
+        self.base
+    }
+
+    pub fn range(&self) -> Range<usize> {
+        self.range.clone()
+    }
+}
+
+impl PointerChain {
+    pub fn root(&self) -> &str {
+        &self.root
+    }
+
+    pub fn base(&self) -> usize {
+        self.base
+    }
+
+    pub fn offset(&self) -> isize {
+        self.offset
+    }
+
+    pub fn offsets(&self) -> &[usize] {
+        &self.offsets
+    }
+
+    pub fn resolve(&self, memory: &Memory) -> Result<usize> {
+        let mut addr = self.base.wrapping_add_signed(self.offset);
+
+        for offset in &self.offsets {
+            let mut buf = [0; POINTER_SIZE];
+
+            memory.read(&mut buf, addr)?;
+            addr = usize::from_ne_bytes(buf) + offset;
+        }
+
+        Ok(addr)
+    }
+}
+
+impl Display for PointerChain {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.offset < 0 {
+            write!(f, "{}-{:#x}", self.root, self.offset.unsigned_abs())?;
+        } else {
+            write!(f, "{}+{:#x}", self.root, self.offset)?;
+        }
+
+        for offset in &self.offsets {
+            write!(f, " -> +{:#x}", offset)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn merge(parts: Vec<Vec<(usize, usize)>>) -> Vec<(usize, usize)> {
+    let mut merged = Vec::with_capacity(parts.iter().map(Vec::len).sum());
+    let mut iters: Vec<_> = parts.into_iter().map(Vec::into_iter).collect();
+    let mut heap: BinaryHeap<_> = iters
+        .iter_mut()
+        .enumerate()
+        .filter_map(|(i, iter)| Some(Reverse((iter.next()?, i))))
+        .collect();
+
+    while let Some(Reverse((pointer, i))) = heap.pop() {
+        merged.push(pointer);
+
+        if let Some(next) = iters[i].next() {
+            heap.push(Reverse((next, i)));
+        }
+    }
+
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hint;
+    use std::process;
+    use std::sync::atomic::AtomicPtr;
+
+    #[repr(C)]
+    struct Outer {
+        padding: [usize; 3],
+        inner: *mut Inner,
+    }
+
+    #[repr(C)]
+    struct Inner {
+        padding: [u64; 8],
+        value: u64,
+    }
+
+    static ROOT: AtomicPtr<Outer> = AtomicPtr::new(std::ptr::null_mut());
+
+    #[test]
+    fn test_pointer_map() {
+        let inner = Box::into_raw(Box::new(Inner {
+            padding: [0; 8],
+            value: 1234,
+        }));
+        let outer = Box::into_raw(Box::new(Outer {
+            padding: [0; 3],
+            inner,
+        }));
+        let target = unsafe { &(*inner).value as *const u64 as usize };
+
+        ROOT.store(hint::black_box(outer), Ordering::SeqCst);
+
+        let id = process::id();
+        let proc = Process::open(id).unwrap();
+        let memory = Memory::options().read(true).open(id).unwrap();
+        let dump = ProcessDump::new(&memory, &proc, |region| region.permissions().write()).unwrap();
+        let roots = Root::modules(&proc).unwrap();
+        let map = PointerMap::new(&dump, roots);
+        let root = ROOT.as_ptr() as usize;
+
+        assert!(!map.is_empty());
+        assert!(map
+            .pointers_to(outer as usize..=outer as usize)
+            .contains(&(outer as usize, root)));
+        assert!(map.roots().iter().any(|r| r.range().contains(&root)));
+
+        let chains = map.find(target, 3, 0x100, usize::MAX);
+        let chain = chains
+            .iter()
+            .find(|chain| chain.base().wrapping_add_signed(chain.offset()) == root)
+            .unwrap();
+        let text = chain.to_string();
+
+        assert_eq!(chain.offsets(), [0x18, 0x40]);
+        assert!(text.ends_with(" -> +0x18 -> +0x40"), "{}", text);
+        assert_eq!(chain.resolve(&memory).unwrap(), target);
+        assert!(chains
+            .windows(2)
+            .all(|w| w[0].offsets().len() <= w[1].offsets().len()));
+        assert_eq!(map.find(target, 3, 0x100, 1).len(), 1);
+        assert!(map.find(usize::MAX, 3, 0x100, usize::MAX).is_empty());
+        assert!(!Root::stacks(&proc).unwrap().is_empty());
+
+        unsafe {
+            drop(Box::from_raw(outer));
+            drop(Box::from_raw(inner));
+        }
+    }
+}
@@ -1,6 +1,14 @@
+use crate::error::{Error, Result};
+use crate::memory::page_size;
 use crate::sys::process as process_imp;
-use std::io;
+use crate::thread::Threads;
+use std::fmt::{self, Display, Formatter};
+use std::iter::Peekable;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::SystemTime;
+use std::{fs, io};
 
 pub struct List(process_imp::List);
 
@@ -8,40 +16,201 @@ pub struct Process(process_imp::Process);
 
 pub struct Regions<'a>(process_imp::Regions<'a>);
 
+#[derive(Clone)]
 pub struct Region(process_imp::Region);
 
+pub struct Suspended<'a>(process_imp::Suspended<'a>);
+
+#[derive(Clone)]
 pub struct Permissions(process_imp::Permissions);
 
+pub struct ProcessInfo(process_imp::ProcessInfo);
+
+pub struct Library {
+    handle: usize,
+    module: Module,
+}
+
+pub struct Modules<'a>(Peekable<Regions<'a>>);
+
+pub struct Module {
+    path: PathBuf,
+    segments: Vec<Region>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ModuleAddress {
+    module: String,
+    offset: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RegionKind {
+    File,
+    DeletedFile,
+    Heap,
+    Stack,
+    Vdso,
+    Vvar,
+    Anonymous,
+    NamedAnonymous,
+    Other,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum State {
+    Running,
+    Sleeping,
+    Waiting,
+    Zombie,
+    Stopped,
+    Traced,
+    Dead,
+    Idle,
+    Unknown,
+}
+
 impl Iterator for List {
-    type Item = io::Result<u32>;
+    type Item = Result<u32>;
 
-    fn next(&mut self) -> Option<io::Result<u32>> {
+    fn next(&mut self) -> Option<Result<u32>> {
         self.0.next()
     }
 }
 
 impl Process {
-    pub fn open(id: u32) -> io::Result<Process> {
+    pub fn open(id: u32) -> Result<Process> {
         process_imp::Process::open(id).map(Process)
     }
 
-    pub fn regions(&self) -> io::Result<Regions> {
+    pub fn id(&self) -> u32 {
+        self.0.id()
+    }
+
+    pub fn regions(&self) -> Result<Regions<'_>> {
         self.0.regions().map(Regions)
     }
 
-    pub fn path(&self) -> io::Result<PathBuf> {
+    pub fn path(&self) -> Result<PathBuf> {
         self.0.path()
     }
+
+    pub fn threads(&self) -> Result<Threads<'_>> {
+        let regions = self.regions()?.collect::<Result<_>>()?;
+
+        Ok(Threads::new(self.0.threads()?, regions, self.id()))
+    }
+
+    pub fn suspend(&self) -> Result<Suspended<'_>> {
+        self.0.suspend().map(Suspended)
+    }
+
+    pub fn alloc(&self, size: usize, permissions: &Permissions) -> Result<usize> {
+        let page_size = page_size();
+
+        self.0
+            .alloc(None, size.div_ceil(page_size) * page_size, &permissions.0)
+    }
+
+    pub fn alloc_at(&self, addr: usize, size: usize, permissions: &Permissions) -> Result<usize> {
+        let page_size = page_size();
+
+        if !addr.is_multiple_of(page_size) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "address must be page aligned",
+            )
+            .into());
+        }
+
+        self.0.alloc(
+            Some(addr),
+            size.div_ceil(page_size) * page_size,
+            &permissions.0,
+        )
+    }
+
+    pub fn free(&self, addr: usize, size: usize) -> Result<()> {
+        let page_size = page_size();
+
+        if !addr.is_multiple_of(page_size) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "address must be page aligned",
+            )
+            .into());
+        }
+
+        self.0.free(addr, size.div_ceil(page_size) * page_size)
+    }
+
+    pub fn protect(&self, range: Range<usize>, permissions: &Permissions) -> Result<()> {
+        let page_size = page_size();
+        let start = range.start / page_size * page_size;
+        let end = range.end.div_ceil(page_size) * page_size;
+
+        self.0.protect(start, end - start, &permissions.0)
+    }
+
+    pub(crate) fn write_stopped(
+        &self,
+        buf: &[u8],
+        addr: usize,
+        busy: &[Range<usize>],
+    ) -> Result<()> {
+        self.0.write_stopped(buf, addr, busy)
+    }
+
+    pub fn call(&self, addr: usize, args: &[u64]) -> Result<u64> {
+        self.0.call(addr, args)
+    }
+
+    pub fn inject_library<P: AsRef<Path>>(&self, path: P) -> Result<Library> {
+        let path = path.as_ref();
+        let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_owned());
+        let name = path.file_name().and_then(|name| name.to_str());
+        let handle = self.0.inject_library(&path)?;
+
+        for module in self.modules()? {
+            let module = module?;
+
+            if module.path() == path || name.is_some_and(|name| module.is(name)) {
+                return Ok(Library { handle, module });
+            }
+        }
+
+        self.0.unload_library(handle)?;
+
+        Err(io::Error::new(io::ErrorKind::NotFound, "library not mapped after loading").into())
+    }
+
+    pub fn unload_library(&self, library: Library) -> Result<()> {
+        self.0.unload_library(library.handle)
+    }
+
+    pub fn info(&self) -> Result<ProcessInfo> {
+        self.0.info().map(ProcessInfo)
+    }
+
+    pub fn modules(&self) -> Result<Modules<'_>> {
+        self.regions().map(|regions| Modules(regions.peekable()))
+    }
 }
 
 impl<'a> Iterator for Regions<'a> {
-    type Item = io::Result<Region>;
+    type Item = Result<Region>;
 
-    fn next(&mut self) -> Option<io::Result<Region>> {
+    fn next(&mut self) -> Option<Result<Region>> {
         self.0.next().map(|region| region.map(Region))
     }
 }
 
+impl<'a> Suspended<'a> {
+    pub fn resume(self) {
+        drop(self.0);
+    }
+}
+
 impl Region {
     pub fn start(&self) -> usize {
         self.0.start()
@@ -55,12 +224,40 @@ impl Region {
         Permissions(self.0.permissions())
     }
 
+    pub fn shared(&self) -> bool {
+        self.0.shared()
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.0.offset()
+    }
+
+    pub fn device(&self) -> (u32, u32) {
+        self.0.device()
+    }
+
+    pub fn inode(&self) -> u64 {
+        self.0.inode()
+    }
+
+    pub fn kind(&self) -> RegionKind {
+        self.0.kind()
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.0.name()
+    }
+
     pub fn path(&self) -> Option<&Path> {
         self.0.path()
     }
 }
 
 impl Permissions {
+    pub fn new(read: bool, write: bool, exec: bool) -> Permissions {
+        Permissions(process_imp::Permissions::new(read, write, exec))
+    }
+
     pub fn read(&self) -> bool {
         self.0.read()
     }
@@ -74,26 +271,226 @@ impl Permissions {
     }
 }
 
-pub fn list() -> io::Result<List> {
+impl ProcessInfo {
+    pub fn id(&self) -> u32 {
+        self.0.id()
+    }
+
+    pub fn name(&self) -> &str {
+        self.0.name()
+    }
+
+    pub fn cmdline(&self) -> &[String] {
+        self.0.cmdline()
+    }
+
+    pub fn parent_id(&self) -> u32 {
+        self.0.parent_id()
+    }
+
+    pub fn uid(&self) -> u32 {
+        self.0.uid()
+    }
+
+    pub fn state(&self) -> State {
+        self.0.state()
+    }
+
+    pub fn start_time(&self) -> SystemTime {
+        self.0.start_time()
+    }
+
+    pub fn rss(&self) -> usize {
+        self.0.rss()
+    }
+}
+
+impl<'a> Iterator for Modules<'a> {
+    type Item = Result<Module>;
+
+    fn next(&mut self) -> Option<Result<Module>> {
+        let (path, region) = loop {
+            match self.0.next()? {
+                Ok(region) => {
+                    if let Some(path) = region.path() {
+                        break (path.to_owned(), region);
+                    }
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        };
+
+        let mut segments = vec![region];
+
+        while let Some(Ok(region)) = self.0.next_if(|region| {
+            region
+                .as_ref()
+                .is_ok_and(|region| region.path() == Some(&path))
+        }) {
+            segments.push(region);
+        }
+
+        Some(Ok(Module { path, segments }))
+    }
+}
+
+impl Module {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn name(&self) -> &str {
+        self.path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .unwrap_or_default()
+    }
+
+    pub fn base(&self) -> usize {
+        self.segments[0].start()
+    }
+
+    pub fn end(&self) -> usize {
+        self.segments[self.segments.len() - 1].end()
+    }
+
+    pub fn size(&self) -> usize {
+        self.end() - self.base()
+    }
+
+    pub fn segments(&self) -> &[Region] {
+        &self.segments
+    }
+
+    pub fn contains(&self, addr: usize) -> bool {
+        addr >= self.base() && addr < self.end()
+    }
+
+    pub fn is(&self, name: &str) -> bool {
+        self.name() == name || self.path == Path::new(name)
+    }
+}
+
+impl Library {
+    pub fn handle(&self) -> usize {
+        self.handle
+    }
+
+    pub fn module(&self) -> &Module {
+        &self.module
+    }
+}
+
+impl ModuleAddress {
+    pub fn new(module: &str, offset: usize) -> ModuleAddress {
+        ModuleAddress {
+            module: module.to_owned(),
+            offset,
+        }
+    }
+
+    pub fn from_addr(modules: &[Module], addr: usize) -> Option<ModuleAddress> {
+        let module = modules.iter().find(|module| module.contains(addr))?;
+
+        Some(ModuleAddress::new(module.name(), addr - module.base()))
+    }
+
+    pub fn to_addr(&self, modules: &[Module]) -> Option<usize> {
+        let module = modules.iter().find(|module| module.is(&self.module))?;
+
+        module.base().checked_add(self.offset)
+    }
+
+    pub fn module(&self) -> &str {
+        &self.module
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl Display for ModuleAddress {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}+{:#x}", self.module, self.offset)
+    }
+}
+
+impl FromStr for Permissions {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Permissions> {
+        if !s.chars().all(|c| "rwx-".contains(c)) {
+            return Err(Error::Io(io::Error::new(io::ErrorKind::InvalidInput, s)));
+        }
+
+        Ok(Permissions::new(
+            s.contains('r'),
+            s.contains('w'),
+            s.contains('x'),
+        ))
+    }
+}
+
+impl FromStr for ModuleAddress {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<ModuleAddress> {
+        let invalid = || Error::Io(io::Error::new(io::ErrorKind::InvalidInput, s));
+        let (module, offset) = s
+            .rsplit_once('+')
+            .filter(|(_, offset)| offset.starts_with(|c: char| c.is_ascii_digit()))
+            .unwrap_or((s, "0"));
+
+        let offset = match offset.strip_prefix("0x") {
+            Some(hex) => usize::from_str_radix(hex, 16),
+            None => offset.parse(),
+        };
+
+        if module.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(ModuleAddress::new(module, offset.map_err(|_| invalid())?))
+    }
+}
+
+pub fn list() -> Result<List> {
     process_imp::list().map(List)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    use crate::debug::Debugger;
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    use crate::memory::Memory;
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    use crate::testing::{self, Child};
     use std::collections::HashSet;
+    #[cfg(target_os = "linux")]
+    use std::fs::{self, File};
+    #[cfg(unix)]
+    use std::os::unix;
+    #[cfg(target_os = "linux")]
+    use std::os::unix::fs::MetadataExt;
+    #[cfg(target_os = "linux")]
+    use std::os::unix::io::AsRawFd;
     use std::{env, process, ptr};
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    use std::{thread, time::Duration};
 
     fn find_region(regions: &[Region], addr: usize) -> &Region {
         regions
-            .into_iter()
+            .iter()
             .find(|region| region.start() <= addr && addr < region.end())
             .unwrap()
     }
 
     #[test]
     fn test_list() {
-        let ids: Vec<_> = list().unwrap().collect::<io::Result<_>>().unwrap();
+        let ids: Vec<_> = list().unwrap().collect::<Result<_>>().unwrap();
         let unique_ids: HashSet<_> = ids.iter().copied().collect();
 
         assert!(ids.contains(&process::id()));
@@ -109,7 +506,7 @@ mod tests {
         let heap_var = Box::leak(Box::new(0));
 
         let proc = Process::open(process::id()).unwrap();
-        let regions: Vec<_> = proc.regions().unwrap().collect::<io::Result<_>>().unwrap();
+        let regions: Vec<_> = proc.regions().unwrap().collect::<Result<_>>().unwrap();
         let exe_path = env::current_exe().unwrap();
         let exe_name = exe_path.file_name().unwrap();
         let mut start = 0;
@@ -119,8 +516,8 @@ mod tests {
             start = region.end();
         }
 
-        let text = find_region(&regions, test_process_regions as usize);
-        let data = find_region(&regions, unsafe { ptr::addr_of!(DATA_VAR) } as usize);
+        let text = find_region(&regions, test_process_regions as fn() as usize);
+        let data = find_region(&regions, ptr::addr_of!(DATA_VAR) as usize);
         let rodata = find_region(&regions, ptr::addr_of!(RODATA_VAR) as usize);
         let stack = find_region(&regions, ptr::addr_of!(stack_var) as usize);
         let heap = find_region(&regions, ptr::addr_of!(*heap_var) as usize);
@@ -136,6 +533,102 @@ mod tests {
         assert_eq!(rodata.path().and_then(Path::file_name), Some(exe_name));
         assert_eq!(stack.path(), None);
         assert_eq!(heap.path(), None);
+
+        assert_eq!(text.kind(), RegionKind::File);
+        assert!(!text.shared() && text.inode() != 0);
+        assert!(data.offset() > 0);
+        assert!(regions
+            .iter()
+            .any(|region| region.kind() == RegionKind::Stack));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_region_kinds() {
+        let path = env::temp_dir().join(format!("fraud-motor-{}", process::id()));
+        fs::write(&path, [0; 4096]).unwrap();
+
+        let file = File::open(&path).unwrap();
+        let addr = unsafe {
+            libc::mmap(
+                ptr::null_mut(),
+                4096,
+                libc::PROT_READ,
+                libc::MAP_SHARED,
+                file.as_raw_fd(),
+                0,
+            )
+        } as usize;
+
+        let inode = file.metadata().unwrap().ino();
+        fs::remove_file(&path).unwrap();
+
+        let proc = Process::open(process::id()).unwrap();
+        let regions: Vec<_> = proc.regions().unwrap().collect::<Result<_>>().unwrap();
+        let mapped = find_region(&regions, addr);
+
+        assert_eq!(mapped.kind(), RegionKind::DeletedFile);
+        assert_eq!(mapped.path(), Some(path.as_path()));
+        assert_eq!(mapped.inode(), inode);
+        assert_eq!(mapped.offset(), 0);
+        assert!(mapped.shared());
+
+        unsafe { libc::munmap(addr as *mut libc::c_void, 4096) };
+    }
+
+    #[test]
+    fn test_process_modules() {
+        let proc = Process::open(process::id()).unwrap();
+        let modules: Vec<_> = proc.modules().unwrap().collect::<Result<_>>().unwrap();
+        let exe_path = env::current_exe().unwrap();
+        let text = test_process_modules as fn() as usize;
+        let exe = modules.iter().find(|module| module.contains(text)).unwrap();
+
+        assert_eq!(exe.path(), exe_path);
+        assert_eq!(exe.size(), exe.end() - exe.base());
+        assert!(exe.segments().len() > 1);
+        assert!(exe
+            .segments()
+            .iter()
+            .all(|region| region.path() == Some(exe.path())));
+
+        for pair in modules.windows(2) {
+            assert!(pair[0].end() <= pair[1].base());
+        }
+
+        let addr = ModuleAddress::from_addr(&modules, text).unwrap();
+        assert_eq!(addr.module(), exe.name());
+        assert_eq!(addr.offset(), text - exe.base());
+        assert_eq!(addr.to_addr(&modules), Some(text));
+
+        let parsed: ModuleAddress = addr.to_string().parse().unwrap();
+        assert_eq!(parsed, addr);
+        assert_eq!(parsed.to_addr(&modules), Some(text));
+    }
+
+    #[test]
+    fn test_module_address_parse() {
+        let addr: ModuleAddress = "libgame.so+0x1a2b".parse().unwrap();
+        assert_eq!(addr, ModuleAddress::new("libgame.so", 0x1a2b));
+        assert_eq!(addr.to_string(), "libgame.so+0x1a2b");
+
+        let addr: ModuleAddress = "libgame.so".parse().unwrap();
+        assert_eq!(addr, ModuleAddress::new("libgame.so", 0));
+
+        let addr: ModuleAddress = "libstdc++.so.6+16".parse().unwrap();
+        assert_eq!(addr, ModuleAddress::new("libstdc++.so.6", 16));
+
+        assert!("libgame.so+0xzz".parse::<ModuleAddress>().is_err());
+        assert!("+0x10".parse::<ModuleAddress>().is_err());
+    }
+
+    #[test]
+    fn test_process_gone() {
+        let mut child = process::Command::new("true").spawn().unwrap();
+        let id = child.id();
+
+        child.wait().unwrap();
+        assert!(matches!(Process::open(id), Err(Error::ProcessGone)));
     }
 
     #[test]
@@ -147,4 +640,271 @@ mod tests {
 
         assert_eq!(proc_path.file_name(), Some(exe_name));
     }
+
+    #[test]
+    fn test_process_info() {
+        let proc = Process::open(process::id()).unwrap();
+        let info = proc.info().unwrap();
+        let args: Vec<_> = env::args().collect();
+        let exe_path = env::current_exe().unwrap();
+        let exe_name = exe_path.file_name().unwrap().to_str().unwrap();
+
+        assert_eq!(info.id(), process::id());
+        assert!(exe_name.starts_with(info.name()));
+        assert_eq!(info.cmdline(), args);
+        #[cfg(unix)]
+        assert_eq!(info.parent_id(), unix::process::parent_id());
+        assert!(info.start_time() <= SystemTime::now());
+        assert!(info.rss() > 0);
+    }
+
+    #[test]
+    fn test_process_suspend() {
+        let mut child = process::Command::new("sleep").arg("10").spawn().unwrap();
+        let proc = Process::open(child.id()).unwrap();
+
+        {
+            let _suspended = proc.suspend().unwrap();
+            assert_eq!(proc.info().unwrap().state(), State::Stopped);
+
+            proc.suspend().unwrap().resume();
+            assert_eq!(proc.info().unwrap().state(), State::Stopped);
+        }
+
+        assert_ne!(proc.info().unwrap().state(), State::Stopped);
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+    }
+
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    #[test]
+    fn test_process_suspend_traced() {
+        let child = Child::fork(testing::idle);
+        let proc = Process::open(child.id()).unwrap();
+        let debugger = Debugger::attach(&proc).unwrap();
+
+        {
+            let _suspended = proc.suspend().unwrap();
+            assert_eq!(proc.info().unwrap().state(), State::Traced);
+
+            proc.suspend().unwrap().resume();
+            assert_eq!(proc.info().unwrap().state(), State::Traced);
+        }
+
+        assert_ne!(proc.info().unwrap().state(), State::Traced);
+        drop(debugger);
+    }
+
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    #[test]
+    fn test_process_alloc() {
+        let page_size = page_size();
+        let child = Child::fork(testing::idle);
+        let proc = Process::open(child.id()).unwrap();
+        let memory = Memory::options()
+            .read(true)
+            .write(true)
+            .open(child.id())
+            .unwrap();
+        let addr = proc.alloc(page_size + 1, &"rw-".parse().unwrap()).unwrap();
+        let find = |addr| {
+            proc.regions()
+                .unwrap()
+                .map(Result::unwrap)
+                .find(|region| region.start() <= addr && addr < region.end())
+        };
+
+        let region = find(addr).unwrap();
+        let perms = region.permissions();
+        assert_eq!(region.start(), addr);
+        assert!(region.end() >= addr + page_size * 2);
+        assert!(perms.read() && perms.write() && !perms.exec());
+
+        let mut buf = [0; 4];
+        memory.write(&[1, 3, 3, 7], addr + page_size).unwrap();
+        memory.read(&mut buf, addr + page_size).unwrap();
+        assert_eq!(buf, [1, 3, 3, 7]);
+
+        proc.protect(addr + 10..addr + 20, &"r-x".parse().unwrap())
+            .unwrap();
+        let region = find(addr).unwrap();
+        let perms = region.permissions();
+        assert_eq!(region.end(), addr + page_size);
+        assert!(perms.read() && !perms.write() && perms.exec());
+
+        proc.free(addr, page_size + 1).unwrap();
+        assert!(find(addr).is_none_or(|region| region.start() != addr));
+        assert!(matches!(
+            proc.free(addr + 1, page_size + 1),
+            Err(Error::Io(err)) if err.kind() == io::ErrorKind::InvalidInput
+        ));
+
+        let perms = "rw-".parse().unwrap();
+        assert_eq!(proc.alloc_at(addr, page_size, &perms).unwrap(), addr);
+        assert!(proc.alloc_at(addr, page_size, &perms).is_err());
+        assert!(proc.alloc_at(addr + 1, page_size, &perms).is_err());
+        proc.free(addr, page_size).unwrap();
+        assert!("rwz".parse::<Permissions>().is_err());
+    }
+
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    #[test]
+    fn test_process_inject_library() {
+        let child = Child::fork(testing::idle);
+        let proc = Process::open(child.id()).unwrap();
+        let loaded = |proc: &Process| {
+            proc.modules()
+                .unwrap()
+                .map(Result::unwrap)
+                .any(|module| module.name() == "libresolv.so.2")
+        };
+
+        assert!(!loaded(&proc));
+        assert!(proc.inject_library("libfraud-motor-missing.so").is_err());
+
+        let library = proc.inject_library("libresolv.so.2").unwrap();
+        assert_ne!(library.handle(), 0);
+        assert_eq!(library.module().name(), "libresolv.so.2");
+        assert!(loaded(&proc));
+
+        proc.unload_library(library).unwrap();
+        assert!(!loaded(&proc));
+        assert_ne!(proc.info().unwrap().state(), State::Zombie);
+    }
+
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    #[test]
+    fn test_process_inject_library_parked() {
+        let child = Child::fork(testing::idle);
+        let proc = Process::open(child.id()).unwrap();
+        let thread = proc.threads().unwrap().next().unwrap().unwrap();
+        let debugger = Debugger::attach(&proc).unwrap();
+        let suspended = proc.suspend().unwrap();
+        let registers = thread.registers().unwrap();
+        let mut parked = registers;
+
+        // dlopen allocates, so it runs the instruction the thread is parked on.
+        parked.rip =
+            libc::malloc as unsafe extern "C" fn(usize) -> *mut libc::c_void as usize as u64;
+        thread.set_registers(&parked).unwrap();
+
+        let library = proc.inject_library("libresolv.so.2").unwrap();
+        assert_eq!(library.module().name(), "libresolv.so.2");
+        assert_eq!(thread.registers().unwrap(), parked);
+        proc.unload_library(library).unwrap();
+
+        thread.set_registers(&registers).unwrap();
+        suspended.resume();
+        drop(debugger);
+        assert_ne!(proc.info().unwrap().state(), State::Zombie);
+    }
+
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    extern "C" fn call_target(a: u64, b: u64, c: u64, d: u64, e: u64, f: u64, g: u64) -> u64 {
+        [a, b, c, d, e, f, g]
+            .iter()
+            .fold(0, |acc, &arg| acc * 10 + arg)
+    }
+
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    #[test]
+    fn test_process_call() {
+        let child = Child::fork(testing::idle);
+        let proc = Process::open(child.id()).unwrap();
+        let thread = proc.threads().unwrap().next().unwrap().unwrap();
+        let registers = thread.registers().unwrap();
+        let addr = call_target as extern "C" fn(u64, u64, u64, u64, u64, u64, u64) -> u64 as usize;
+
+        for _ in 0..2 {
+            let result = proc.call(addr, &[1, 2, 3, 4, 5, 6, 7]).unwrap();
+            assert_eq!(result, 1234567);
+        }
+
+        assert_eq!(thread.registers().unwrap(), registers);
+        assert_ne!(proc.info().unwrap().state(), State::Zombie);
+    }
+
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    extern "C" fn sleep_target() -> u64 {
+        unsafe { libc::usleep(1) };
+        42
+    }
+
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    #[test]
+    fn test_process_call_parked() {
+        let child = Child::fork(testing::idle);
+        let proc = Process::open(child.id()).unwrap();
+        let target = sleep_target as extern "C" fn() -> u64 as usize;
+
+        while proc.info().unwrap().state() != State::Sleeping {
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        for _ in 0..10 {
+            assert_eq!(proc.call(target, &[]).unwrap(), 42);
+        }
+
+        assert_ne!(proc.info().unwrap().state(), State::Zombie);
+    }
+
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    static mut SIGNAL_STATE: u32 = 0;
+
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    extern "C" fn signal_handler(_: libc::c_int) {
+        unsafe { SIGNAL_STATE = 2 };
+    }
+
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    extern "C" fn signal_target() -> u64 {
+        unsafe { libc::raise(libc::SIGUSR1) };
+        42
+    }
+
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    fn signal_child() -> ! {
+        unsafe {
+            libc::signal(
+                libc::SIGUSR1,
+                signal_handler as extern "C" fn(libc::c_int) as libc::sighandler_t,
+            );
+            SIGNAL_STATE = 1;
+        }
+
+        testing::idle()
+    }
+
+    #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+    #[test]
+    fn test_process_call_signal() {
+        let child = Child::fork(signal_child);
+        let proc = Process::open(child.id()).unwrap();
+        let memory = Memory::options().read(true).open(child.id()).unwrap();
+        let addr = ptr::addr_of!(SIGNAL_STATE) as usize;
+        let state = || {
+            let mut buf = [0; 4];
+            memory.read(&mut buf, addr).unwrap();
+            u32::from_ne_bytes(buf)
+        };
+
+        while state() == 0 {
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        let target = signal_target as extern "C" fn() -> u64 as usize;
+        assert_eq!(proc.call(target, &[]).unwrap(), 42);
+
+        for _ in 0..1000 {
+            if state() == 2 {
+                break;
+            }
+
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        assert_eq!(state(), 2);
+        assert_ne!(proc.info().unwrap().state(), State::Zombie);
+    }
 }
@@ -1,7 +1,53 @@
+use crate::dump::ProcessDump;
+use crate::error::{Error, Result};
+use crate::memory::Memory;
+use crate::process::{Module, Process, Region};
+use std::fmt::{self, Display, Formatter};
+use std::io;
+use std::str::FromStr;
+
 pub struct Scan {
     regions: Vec<(usize, Box<[u8]>)>,
     align: usize,
     len: usize,
+    previous: Option<Previous>,
+}
+
+struct Previous {
+    size: usize,
+    values: Vec<u8>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Pattern {
+    bytes: Vec<u8>,
+    masks: Vec<u8>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    Latin1,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TextPattern {
+    units: Vec<Vec<Vec<u8>>>,
+    encoding: Encoding,
+    len: usize,
+    null_terminated: bool,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum FloatMode {
+    #[default]
+    Exact,
+    Absolute(f64),
+    Relative(f64),
+    Rounded(u32),
+    Truncated(u32),
 }
 
 pub struct Iter<'a> {
@@ -19,31 +65,99 @@ impl Scan {
             regions: Vec::new(),
             align,
             len: 0,
+            previous: None,
         }
     }
 
     pub fn insert(&mut self, start: usize, end: usize) {
-        let size = (end - start + self.align - 1) / self.align;
-        let mut data = vec![255; (size + 7) / 8].into_boxed_slice();
+        let size = (end - start).div_ceil(self.align);
+        let mut data = vec![255; size.div_ceil(8)].into_boxed_slice();
 
-        if size % 8 != 0 {
+        if !size.is_multiple_of(8) {
             data[size / 8] >>= 8 - size % 8;
         }
 
         self.regions.push((start, data));
         self.len += size;
+        self.previous = None;
     }
 
     pub fn retain<F: FnMut(usize) -> bool>(&mut self, mut filter: F) {
+        self.retain_with_previous(|addr, _| filter(addr));
+    }
+
+    pub fn remember(&mut self, current: &ProcessDump, size: usize) {
+        let mut current = current.view();
+        let mut values = Vec::new();
+
+        self.previous = None;
+        self.retain(
+            |addr| match current.data(addr).and_then(|data| data.get(..size)) {
+                Some(data) => {
+                    values.extend_from_slice(data);
+                    true
+                }
+                None => false,
+            },
+        );
+        self.previous = Some(Previous { size, values });
+    }
+
+    pub fn has_previous(&self) -> bool {
+        self.previous.is_some()
+    }
+
+    pub fn retain_previous<F: FnMut(&[u8], &[u8]) -> bool>(
+        &mut self,
+        current: &ProcessDump,
+        mut filter: F,
+    ) {
+        let Some(size) = self.previous.as_ref().map(|previous| previous.size) else {
+            return;
+        };
+
+        let mut current = current.view();
+
+        self.retain_with_previous(|addr, previous| {
+            let current = current.data(addr).and_then(|data| data.get(..size));
+
+            previous
+                .zip(current)
+                .is_some_and(|(previous, current)| filter(previous, current))
+        });
+    }
+
+    fn retain_with_previous<F: FnMut(usize, Option<&[u8]>) -> bool>(&mut self, mut filter: F) {
+        let mut index = 0;
+        let mut kept = 0;
+
         for &mut (mut addr, ref mut data) in self.regions.iter_mut() {
             for byte in data.iter_mut() {
                 if *byte != 0 {
                     for bit in 0..8 {
                         let mask = 1 << bit;
 
-                        if *byte & mask != 0 && !filter(addr) {
-                            *byte &= !mask;
-                            self.len -= 1;
+                        if *byte & mask != 0 {
+                            let value = self.previous.as_ref().map(|previous| {
+                                &previous.values[index * previous.size..][..previous.size]
+                            });
+
+                            if filter(addr, value) {
+                                if let Some(previous) = &mut self.previous {
+                                    let size = previous.size;
+
+                                    previous
+                                        .values
+                                        .copy_within(index * size..(index + 1) * size, kept * size);
+                                }
+
+                                kept += 1;
+                            } else {
+                                *byte &= !mask;
+                                self.len -= 1;
+                            }
+
+                            index += 1;
                         }
 
                         addr += self.align;
@@ -53,9 +167,33 @@ impl Scan {
                 }
             }
         }
+
+        if let Some(previous) = &mut self.previous {
+            previous.values.truncate(kept * previous.size);
+        }
+    }
+
+    pub fn retain_changes<F: FnMut(&[u8], &[u8]) -> bool>(
+        &mut self,
+        previous: &ProcessDump,
+        current: &ProcessDump,
+        size: usize,
+        mut filter: F,
+    ) {
+        let mut previous = previous.view();
+        let mut current = current.view();
+
+        self.retain(|addr| {
+            let previous = previous.data(addr).and_then(|data| data.get(..size));
+            let current = current.data(addr).and_then(|data| data.get(..size));
+
+            previous
+                .zip(current)
+                .is_some_and(|(previous, current)| filter(previous, current))
+        });
     }
 
-    pub fn iter(&self) -> Iter {
+    pub fn iter(&self) -> Iter<'_> {
         Iter {
             regions: &self.regions,
             align: self.align,
@@ -114,13 +252,405 @@ impl<'a> Iterator for Iter<'a> {
     }
 }
 
+impl Pattern {
+    pub fn new(bytes: &[u8], masks: &[u8]) -> Pattern {
+        assert_eq!(bytes.len(), masks.len());
+
+        Pattern {
+            bytes: bytes
+                .iter()
+                .zip(masks)
+                .map(|(byte, mask)| byte & mask)
+                .collect(),
+            masks: masks.to_owned(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    pub fn matches(&self, data: &[u8]) -> bool {
+        data.len() >= self.len()
+            && data
+                .iter()
+                .zip(self.bytes.iter().zip(&self.masks))
+                .all(|(data, (byte, mask))| data & mask == *byte)
+    }
+
+    pub fn find(&self, data: &[u8], addr: usize) -> Vec<usize> {
+        let mut matches = Vec::new();
+
+        if self.is_empty() || data.len() < self.len() {
+            return matches;
+        }
+
+        let last = data.len() - self.len();
+
+        match self.masks.iter().position(|&mask| mask == 0xff) {
+            Some(anchor) => {
+                let mut offset = 0;
+
+                while let Some(i) = data[anchor + offset..=anchor + last]
+                    .iter()
+                    .position(|&byte| byte == self.bytes[anchor])
+                {
+                    offset += i;
+
+                    if self.matches(&data[offset..]) {
+                        matches.push(addr + offset);
+                    }
+
+                    offset += 1;
+                }
+            }
+            None => {
+                for offset in 0..=last {
+                    if self.matches(&data[offset..]) {
+                        matches.push(addr + offset);
+                    }
+                }
+            }
+        }
+
+        matches
+    }
+
+    pub fn scan_dump(&self, dump: &ProcessDump) -> Vec<usize> {
+        scan_dump(dump, |data, addr| self.find(data, addr))
+    }
+
+    pub fn scan_memory<F: FnMut(&Region) -> bool>(
+        &self,
+        memory: &Memory,
+        process: &Process,
+        mut filter: F,
+    ) -> Result<Vec<usize>> {
+        let mut matches = Vec::new();
+
+        for region in process.regions()? {
+            let region = region?;
+
+            if filter(&region) {
+                matches.extend(self.scan_region(memory, &region)?);
+            }
+        }
+
+        Ok(matches)
+    }
+
+    pub fn scan_module(&self, memory: &Memory, module: &Module) -> Result<Vec<usize>> {
+        let mut matches = Vec::new();
+
+        for region in module.segments() {
+            if region.permissions().exec() {
+                matches.extend(self.scan_region(memory, region)?);
+            }
+        }
+
+        Ok(matches)
+    }
+
+    fn scan_region(&self, memory: &Memory, region: &Region) -> Result<Vec<usize>> {
+        let mut buf = vec![0; region.end() - region.start()];
+        let mut matches = Vec::new();
+
+        for range in memory.read_partial(&mut buf, region.start())? {
+            let start = range.start - region.start();
+            let end = range.end - region.start();
+
+            matches.extend(self.find(&buf[start..end], range.start));
+        }
+
+        Ok(matches)
+    }
+}
+
+impl FromStr for Pattern {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Pattern> {
+        let invalid = || {
+            Error::Io(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid pattern",
+            ))
+        };
+        let mut bytes = Vec::new();
+        let mut masks = Vec::new();
+
+        for token in s.split_whitespace() {
+            let token = if token == "?" { "??" } else { token };
+
+            if !token.is_ascii() || token.len() % 2 != 0 {
+                return Err(invalid());
+            }
+
+            for pair in token.as_bytes().chunks(2) {
+                let mut byte = 0;
+                let mut mask = 0;
+
+                for &c in pair {
+                    byte <<= 4;
+                    mask <<= 4;
+
+                    if c != b'?' {
+                        byte |= (c as char).to_digit(16).ok_or_else(invalid)? as u8;
+                        mask |= 0xf;
+                    }
+                }
+
+                bytes.push(byte);
+                masks.push(mask);
+            }
+        }
+
+        if bytes.is_empty() {
+            return Err(invalid());
+        }
+
+        Ok(Pattern { bytes, masks })
+    }
+}
+
+impl FloatMode {
+    pub fn eq(&self, value: f64, target: f64) -> bool {
+        if value.is_nan() || target.is_nan() {
+            return value.is_nan() && target.is_nan();
+        }
+
+        if value.is_infinite() || target.is_infinite() {
+            return value == target;
+        }
+
+        match *self {
+            FloatMode::Exact => value == target,
+            FloatMode::Absolute(epsilon) => (value - target).abs() <= epsilon,
+            FloatMode::Relative(epsilon) => {
+                (value - target).abs() <= epsilon * value.abs().max(target.abs())
+            }
+            FloatMode::Rounded(decimals) => {
+                let scale = 10f64.powi(decimals as i32);
+
+                (value * scale).round() == (target * scale).round()
+            }
+            FloatMode::Truncated(decimals) => {
+                let scale = 10f64.powi(decimals as i32);
+
+                (value * scale).trunc() == (target * scale).trunc()
+            }
+        }
+    }
+}
+
+impl Encoding {
+    pub const ALL: [Encoding; 4] = [
+        Encoding::Utf8,
+        Encoding::Utf16Le,
+        Encoding::Utf16Be,
+        Encoding::Latin1,
+    ];
+
+    pub fn unit_size(&self) -> usize {
+        match self {
+            Encoding::Utf8 | Encoding::Latin1 => 1,
+            Encoding::Utf16Le | Encoding::Utf16Be => 2,
+        }
+    }
+
+    pub fn encode(&self, c: char) -> Option<Vec<u8>> {
+        match self {
+            Encoding::Utf8 => Some(c.to_string().into_bytes()),
+            Encoding::Utf16Le => Some(
+                c.encode_utf16(&mut [0; 2])
+                    .iter()
+                    .flat_map(|unit| unit.to_le_bytes())
+                    .collect(),
+            ),
+            Encoding::Utf16Be => Some(
+                c.encode_utf16(&mut [0; 2])
+                    .iter()
+                    .flat_map(|unit| unit.to_be_bytes())
+                    .collect(),
+            ),
+            Encoding::Latin1 => u8::try_from(c).ok().map(|byte| vec![byte]),
+        }
+    }
+
+    pub fn decode_lossy(&self, bytes: &[u8]) -> String {
+        match self {
+            Encoding::Utf8 => String::from_utf8_lossy(bytes).into_owned(),
+            Encoding::Utf16Le | Encoding::Utf16Be => {
+                let units = bytes.chunks_exact(2).map(|unit| {
+                    let unit = [unit[0], unit[1]];
+
+                    if *self == Encoding::Utf16Le {
+                        u16::from_le_bytes(unit)
+                    } else {
+                        u16::from_be_bytes(unit)
+                    }
+                });
+
+                char::decode_utf16(units)
+                    .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                    .collect()
+            }
+            Encoding::Latin1 => bytes.iter().map(|&byte| byte as char).collect(),
+        }
+    }
+}
+
+impl Display for Encoding {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Encoding::Utf8 => "utf8",
+            Encoding::Utf16Le => "utf16le",
+            Encoding::Utf16Be => "utf16be",
+            Encoding::Latin1 => "latin1",
+        })
+    }
+}
+
+impl FromStr for Encoding {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Encoding> {
+        match s {
+            "utf8" => Ok(Encoding::Utf8),
+            "utf16le" => Ok(Encoding::Utf16Le),
+            "utf16be" => Ok(Encoding::Utf16Be),
+            "latin1" => Ok(Encoding::Latin1),
+            _ => Err(io::Error::new(io::ErrorKind::InvalidInput, "unknown encoding").into()),
+        }
+    }
+}
+
+impl TextPattern {
+    pub fn new(
+        text: &str,
+        encoding: Encoding,
+        ignore_case: bool,
+        null_terminated: bool,
+    ) -> Option<TextPattern> {
+        let mut units = Vec::new();
+
+        for c in text.chars() {
+            let bytes = encoding.encode(c)?;
+            let mut alternatives = vec![bytes];
+
+            if ignore_case {
+                for variant in [single(c.to_lowercase()), single(c.to_uppercase())] {
+                    let variant = variant.and_then(|variant| encoding.encode(variant));
+
+                    if let Some(variant) = variant.filter(|variant| {
+                        variant.len() == alternatives[0].len() && !alternatives.contains(variant)
+                    }) {
+                        alternatives.push(variant);
+                    }
+                }
+            }
+
+            units.push(alternatives);
+        }
+
+        if units.is_empty() {
+            return None;
+        }
+
+        Some(TextPattern {
+            len: units.iter().map(|unit| unit[0].len()).sum(),
+            units,
+            encoding,
+            null_terminated,
+        })
+    }
+
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn matches(&self, data: &[u8]) -> bool {
+        let mut offset = 0;
+
+        for unit in &self.units {
+            let len = unit[0].len();
+
+            match data.get(offset..offset + len) {
+                Some(bytes) if unit.iter().any(|alternative| alternative == bytes) => offset += len,
+                _ => return false,
+            }
+        }
+
+        !self.null_terminated
+            || data
+                .get(offset..offset + self.encoding.unit_size())
+                .is_some_and(|terminator| terminator.iter().all(|&byte| byte == 0))
+    }
+
+    pub fn find(&self, data: &[u8], addr: usize) -> Vec<usize> {
+        let first: Vec<u8> = self.units[0].iter().map(|bytes| bytes[0]).collect();
+
+        data.iter()
+            .enumerate()
+            .filter(|&(offset, byte)| first.contains(byte) && self.matches(&data[offset..]))
+            .map(|(offset, _)| addr + offset)
+            .collect()
+    }
+
+    pub fn scan_dump(&self, dump: &ProcessDump) -> Vec<usize> {
+        scan_dump(dump, |data, addr| self.find(data, addr))
+    }
+}
+
+fn scan_dump<F: FnMut(&[u8], usize) -> Vec<usize>>(dump: &ProcessDump, mut find: F) -> Vec<usize> {
+    let mut matches = Vec::new();
+
+    for (region, data) in dump.regions() {
+        if let Ok(data) = data {
+            for range in data.readable() {
+                matches.extend(find(
+                    &data.data()[range.clone()],
+                    region.start() + range.start,
+                ));
+            }
+        }
+    }
+
+    matches
+}
+
+fn single<I: Iterator<Item = char>>(mut chars: I) -> Option<char> {
+    let c = chars.next()?;
+
+    chars.next().is_none().then_some(c)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::dump::ProcessDump;
-    use crate::memory::Memory;
-    use crate::process::{Process, Region};
-    use std::{process, ptr};
+    use crate::value::{Type, Value};
+    use std::{hint, process, ptr};
+
+    static SIGNATURE: [u8; 12] = [
+        0xde, 0xad, 0x48, 0x8b, 0x05, 0x12, 0x34, 0x56, 0x78, 0x41, 0x89, 0xbe,
+    ];
+
+    #[inline(never)]
+    fn signature_target(i: u64) -> u64 {
+        hint::black_box(i).wrapping_mul(0x1234_5678_9abc_def1) ^ 0x0fed_cba9_8765_4321
+    }
 
     fn region_filter(region: &Region, addr: usize) -> bool {
         region.permissions().write() && addr >= region.start() && addr < region.end()
@@ -144,7 +674,7 @@ mod tests {
 
             if region_filter(&region, secret_addr) {
                 scan.insert(region.start(), region.end());
-                size += (region.end() - region.start() + 3) / 4;
+                size += (region.end() - region.start()).div_ceil(4);
             }
         }
 
@@ -179,4 +709,260 @@ mod tests {
         assert!(scan.len() <= 2);
         assert!(!scan.is_empty());
     }
+
+    #[test]
+    fn test_scan_changes() {
+        let id = process::id();
+        let memory = Memory::options().read(true).open(id).unwrap();
+        let proc = Process::open(id).unwrap();
+        let mut values = Box::new([10u32, 20, 30, 40]);
+        let addr = values.as_ptr() as usize;
+        let filter = |region: &Region| region_filter(region, addr);
+        let mut scan = Scan::new(4);
+
+        scan.insert(addr, addr + 16);
+
+        let previous = ProcessDump::new(&memory, &proc, filter).unwrap();
+
+        values[1] += 5;
+        values[2] -= 5;
+        values[3] += 1;
+        hint::black_box(&mut values);
+
+        let current = ProcessDump::new(&memory, &proc, filter).unwrap();
+        let cvt = |buf: &[u8]| u32::from_ne_bytes(buf.try_into().unwrap());
+
+        scan.retain_changes(&previous, &current, 4, |a, b| a != b);
+        assert_eq!(
+            scan.iter().collect::<Vec<_>>(),
+            [addr + 4, addr + 8, addr + 12]
+        );
+
+        scan.retain_changes(&previous, &current, 4, |a, b| cvt(b) > cvt(a));
+        assert_eq!(scan.iter().collect::<Vec<_>>(), [addr + 4, addr + 12]);
+
+        scan.retain_changes(&previous, &current, 4, |a, b| cvt(b) == cvt(a) + 5);
+        assert_eq!(scan.iter().collect::<Vec<_>>(), [addr + 4]);
+
+        scan.retain_changes(&current, &current, 4, |a, b| a != b);
+        assert!(scan.is_empty());
+    }
+
+    #[test]
+    fn test_scan_previous() {
+        let id = process::id();
+        let memory = Memory::options().read(true).open(id).unwrap();
+        let proc = Process::open(id).unwrap();
+        let mut values = Box::new([10u32, 20, 30, 40]);
+        let addr = values.as_ptr() as usize;
+        let filter = |region: &Region| region_filter(region, addr);
+        let dump = || ProcessDump::new(&memory, &proc, filter).unwrap();
+        let cvt = |buf: &[u8]| u32::from_ne_bytes(buf.try_into().unwrap());
+        let mut scan = Scan::new(4);
+
+        scan.insert(addr, addr + 16);
+        scan.retain_previous(&dump(), |_, _| false);
+        assert_eq!(scan.len(), 4);
+        assert!(!scan.has_previous());
+
+        scan.remember(&dump(), 4);
+        assert!(scan.has_previous());
+
+        values[0] += 1;
+        values[2] += 5;
+        values[3] += 5;
+        hint::black_box(&mut values);
+
+        let current = dump();
+
+        scan.retain(|other| other != addr + 8);
+        scan.retain_previous(&current, |a, b| cvt(b) == cvt(a) + 5);
+        assert_eq!(scan.iter().collect::<Vec<_>>(), [addr + 12]);
+
+        scan.remember(&current, 4);
+        values[3] -= 1;
+        hint::black_box(&mut values);
+
+        scan.retain_previous(&dump(), |a, b| cvt(b) < cvt(a));
+        assert_eq!(scan.iter().collect::<Vec<_>>(), [addr + 12]);
+
+        scan.remember(&dump(), 4);
+        scan.insert(addr, addr + 4);
+        assert!(!scan.has_previous());
+        scan.retain(|_| true);
+        assert_eq!(scan.len(), 2);
+    }
+
+    #[test]
+    fn test_scan_big_endian() {
+        let id = process::id();
+        let memory = Memory::options().read(true).open(id).unwrap();
+        let proc = Process::open(id).unwrap();
+        let mut values = Box::new([1000u32.to_be(), 1000, 2000u32.to_be(), 1000u32.to_be()]);
+        let addr = values.as_ptr() as usize;
+        let filter = |region: &Region| region_filter(region, addr);
+        let dump = || ProcessDump::new(&memory, &proc, filter).unwrap();
+        let value = |buf: &[u8]| Type::U32.from_be_bytes(buf);
+        let mut scan = Scan::new(4);
+
+        scan.insert(addr, addr + 16);
+
+        let previous = dump();
+        let mut view = previous.view();
+
+        scan.retain(|addr| {
+            view.data(addr)
+                .is_some_and(|buf| value(buf) == Value::U32(1000))
+        });
+        assert_eq!(scan.iter().collect::<Vec<_>>(), [addr, addr + 12]);
+
+        values[3] = 1500u32.to_be();
+        hint::black_box(&mut values);
+
+        scan.retain_changes(&previous, &dump(), 4, |a, b| value(b) > value(a));
+        assert_eq!(scan.iter().collect::<Vec<_>>(), [addr + 12]);
+    }
+
+    #[test]
+    fn test_float_mode() {
+        let nan = f64::NAN;
+        let inf = f64::INFINITY;
+
+        assert!(FloatMode::Exact.eq(99.5, 99.5));
+        assert!(!FloatMode::Exact.eq(99.50001, 99.5));
+        assert!(FloatMode::Absolute(0.01).eq(99.505, 99.5));
+        assert!(!FloatMode::Absolute(0.01).eq(99.52, 99.5));
+        assert!(FloatMode::Relative(0.001).eq(1000.9, 1000.0));
+        assert!(!FloatMode::Relative(0.001).eq(1.1, 1.0));
+        assert!(FloatMode::Rounded(1).eq(99.54, 99.5));
+        assert!(!FloatMode::Rounded(1).eq(99.56, 99.5));
+        assert!(FloatMode::Truncated(1).eq(99.59, 99.5));
+        assert!(!FloatMode::Truncated(1).eq(99.49, 99.5));
+        assert!(FloatMode::Truncated(1).eq(99.51, 99.58));
+        assert!(!FloatMode::Truncated(1).eq(99.61, 99.58));
+        assert!(FloatMode::Rounded(0).eq(99.6f32 as f64, 100.0));
+
+        for mode in [
+            FloatMode::Exact,
+            FloatMode::Absolute(1.0),
+            FloatMode::Relative(1.0),
+            FloatMode::Rounded(2),
+            FloatMode::Truncated(2),
+        ] {
+            assert!(mode.eq(nan, nan));
+            assert!(!mode.eq(nan, 1.0));
+            assert!(!mode.eq(1.0, nan));
+            assert!(mode.eq(inf, inf));
+            assert!(!mode.eq(-inf, inf));
+            assert!(!mode.eq(f64::MAX, inf));
+        }
+    }
+
+    #[test]
+    fn test_pattern() {
+        let pattern: Pattern = "48 8B 05 ?? ?? ?? ?? 4? 89".parse().unwrap();
+        let data = [
+            0x90, 0x48, 0x8b, 0x05, 1, 2, 3, 4, 0x41, 0x89, 0x48, 0x8b, 0x05,
+        ];
+
+        assert_eq!(pattern.len(), 9);
+        assert_eq!(pattern.find(&data, 0x1000), [0x1001]);
+        assert!(pattern.find(&data[..9], 0).is_empty());
+        assert!(!pattern.matches(&[0x48, 0x8b, 0x05, 1, 2, 3, 4, 0x51, 0x89]));
+        assert_eq!(
+            "?8 ?"
+                .parse::<Pattern>()
+                .unwrap()
+                .find(&[0x18, 0, 0x28, 5], 0),
+            [0, 2]
+        );
+        assert_eq!(
+            "488b05".parse::<Pattern>().unwrap(),
+            "48 8b 05".parse().unwrap()
+        );
+        assert_eq!(Pattern::new(&[0x12], &[0xf0]), "1?".parse().unwrap());
+        assert!("".parse::<Pattern>().is_err());
+        assert!("4".parse::<Pattern>().is_err());
+        assert!("zz".parse::<Pattern>().is_err());
+
+        let id = process::id();
+        let memory = Memory::options().read(true).open(id).unwrap();
+        let proc = Process::open(id).unwrap();
+        let dump =
+            ProcessDump::new(&memory, &proc, |region| !region.permissions().write()).unwrap();
+        let signature: Pattern = "de ad 48 8b 05 ?? ?? ?? ?? 4? 89 be".parse().unwrap();
+        let addr = SIGNATURE.as_ptr() as usize;
+
+        assert!(signature.scan_dump(&dump).contains(&addr));
+        assert!(signature
+            .scan_memory(&memory, &proc, |region| region.start() <= addr
+                && addr < region.end())
+            .unwrap()
+            .contains(&addr));
+
+        let target = signature_target as fn(u64) -> u64 as usize;
+        let module = proc
+            .modules()
+            .unwrap()
+            .map(Result::unwrap)
+            .find(|module| module.contains(target))
+            .unwrap();
+        let mut code = [0; 16];
+
+        hint::black_box(signature_target(1));
+        memory.read(&mut code, target).unwrap();
+
+        let code = code
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect::<Vec<_>>();
+        let pattern: Pattern = code.join(" ").parse().unwrap();
+
+        assert!(pattern
+            .scan_module(&memory, &module)
+            .unwrap()
+            .contains(&target));
+    }
+
+    #[test]
+    fn test_text_pattern() {
+        let utf16: Vec<u8> = "xHéllo\0"
+            .encode_utf16()
+            .flat_map(|unit| unit.to_le_bytes())
+            .collect();
+        let pattern = TextPattern::new("héllo", Encoding::Utf16Le, true, true).unwrap();
+
+        assert_eq!(pattern.len(), 10);
+        assert_eq!(pattern.find(&utf16, 0x1000), [0x1002]);
+        assert!(pattern.find(&utf16[..12], 0).is_empty());
+        assert!(TextPattern::new("héllo", Encoding::Utf16Le, false, false)
+            .unwrap()
+            .find(&utf16, 0)
+            .is_empty());
+
+        let latin1 = [b'a', 0xc4, b'B', b'c', 0xe4, b'b', b'C'];
+        let pattern = TextPattern::new("äbc", Encoding::Latin1, true, false).unwrap();
+
+        assert_eq!(pattern.find(&latin1, 0), [1, 4]);
+        assert!(TextPattern::new("€", Encoding::Latin1, false, false).is_none());
+        assert!(TextPattern::new("", Encoding::Utf8, false, false).is_none());
+        assert_eq!(
+            TextPattern::new("ab", Encoding::Utf16Be, false, false)
+                .unwrap()
+                .find(&[0, b'a', 0, b'b', 0, b'a'], 0),
+            [0]
+        );
+        assert_eq!(Encoding::Utf16Be.decode_lossy(&[0, b'h', 0, b'i']), "hi");
+        assert_eq!("utf16le".parse::<Encoding>().unwrap(), Encoding::Utf16Le);
+        assert_eq!(Encoding::Latin1.to_string(), "latin1");
+
+        let text = Box::new(*b"fraud-motor-string\0");
+        let id = process::id();
+        let memory = Memory::options().read(true).open(id).unwrap();
+        let proc = Process::open(id).unwrap();
+        let dump = ProcessDump::new(&memory, &proc, |region| region.permissions().write()).unwrap();
+        let pattern = TextPattern::new("FRAUD-MOTOR-STRING", Encoding::Utf8, true, true).unwrap();
+
+        assert!(pattern.scan_dump(&dump).contains(&(text.as_ptr() as usize)));
+    }
 }
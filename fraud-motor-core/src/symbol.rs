@@ -0,0 +1,265 @@
+use crate::elf::Elf;
+use crate::error::Result;
+use crate::process::{Module, Process};
+use std::fmt::{self, Display, Formatter};
+
+pub struct Symbols {
+    symbols: Vec<Symbol>,
+    ends: Vec<usize>,
+}
+
+pub struct Symbol {
+    name: String,
+    demangled: Option<String>,
+    module: String,
+    addr: usize,
+    size: usize,
+    func: bool,
+}
+
+pub struct SymbolAddress<'a> {
+    symbol: &'a Symbol,
+    offset: usize,
+}
+
+impl Symbols {
+    pub fn load(process: &Process) -> Result<Symbols> {
+        let modules: Vec<_> = process.modules()?.collect::<Result<_>>()?;
+
+        Ok(Symbols::from_modules(&modules))
+    }
+
+    pub fn from_modules(modules: &[Module]) -> Symbols {
+        let mut symbols = Vec::new();
+
+        for module in modules {
+            if let Ok(elf) = Elf::open(module.path()) {
+                let bias = module.base().wrapping_sub(elf.base() as usize);
+
+                for symbol in elf.symbols() {
+                    symbols.push(Symbol {
+                        name: symbol.name().to_owned(),
+                        demangled: demangle(symbol.name()),
+                        module: module.name().to_owned(),
+                        addr: bias.wrapping_add(symbol.value() as usize),
+                        size: symbol.size() as usize,
+                        func: symbol.func(),
+                    });
+                }
+            }
+        }
+
+        Symbols::new(symbols)
+    }
+
+    fn new(mut symbols: Vec<Symbol>) -> Symbols {
+        symbols.sort_by(|a, b| (a.addr, &a.name).cmp(&(b.addr, &b.name)));
+        symbols.dedup_by(|a, b| (a.addr, &a.name) == (b.addr, &b.name));
+
+        let ends = symbols
+            .iter()
+            .scan(0, |end, symbol| {
+                *end = symbol.end().max(*end);
+                Some(*end)
+            })
+            .collect();
+
+        Symbols { symbols, ends }
+    }
+
+    pub fn lookup(&self, addr: usize) -> Option<SymbolAddress<'_>> {
+        let end = self.symbols.partition_point(|symbol| symbol.addr <= addr);
+
+        let symbol = self.symbols[..end]
+            .iter()
+            .zip(&self.ends)
+            .rev()
+            .take_while(|&(_, &end)| end > addr)
+            .map(|(symbol, _)| symbol)
+            .find(|symbol| addr < symbol.end())?;
+
+        Some(SymbolAddress {
+            symbol,
+            offset: addr - symbol.addr,
+        })
+    }
+
+    pub fn find(&self, name: &str) -> Option<&Symbol> {
+        self.symbols
+            .iter()
+            .find(|symbol| symbol.name == name)
+            .or_else(|| {
+                self.symbols
+                    .iter()
+                    .find(|symbol| symbol.demangled.as_deref() == Some(name))
+            })
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Symbol> {
+        self.symbols.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.symbols.is_empty()
+    }
+}
+
+impl Symbol {
+    pub fn name(&self) -> &str {
+        self.demangled.as_deref().unwrap_or(&self.name)
+    }
+
+    pub fn raw_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn module(&self) -> &str {
+        &self.module
+    }
+
+    pub fn addr(&self) -> usize {
+        self.addr
+    }
+
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    pub fn func(&self) -> bool {
+        self.func
+    }
+
+    fn end(&self) -> usize {
+        self.addr.saturating_add(self.size.max(1))
+    }
+}
+
+impl<'a> SymbolAddress<'a> {
+    pub fn symbol(&self) -> &'a Symbol {
+        self.symbol
+    }
+
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<'a> Display for SymbolAddress<'a> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.offset == 0 {
+            write!(f, "{}", self.symbol.name())
+        } else {
+            write!(f, "{}+{:#x}", self.symbol.name(), self.offset)
+        }
+    }
+}
+
+fn demangle(name: &str) -> Option<String> {
+    if let Ok(demangled) = rustc_demangle::try_demangle(name) {
+        return Some(format!("{:#}", demangled));
+    }
+
+    cpp_demangle::Symbol::new(name)
+        .ok()
+        .and_then(|symbol| symbol.demangle(&Default::default()).ok())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{mem, process};
+
+    #[no_mangle]
+    #[inline(never)]
+    extern "C" fn fraud_motor_test_symbol() -> u32 {
+        1337
+    }
+
+    #[inline(never)]
+    fn rust_test_symbol() -> u32 {
+        fraud_motor_test_symbol() + 1
+    }
+
+    #[test]
+    fn test_symbols_lookup() {
+        let proc = Process::open(process::id()).unwrap();
+        let symbols = Symbols::load(&proc).unwrap();
+        let addr = fraud_motor_test_symbol as extern "C" fn() -> u32 as usize;
+
+        assert!(!symbols.is_empty());
+        assert_eq!(rust_test_symbol(), 1338);
+
+        let found = symbols.lookup(addr).unwrap();
+        assert_eq!(found.symbol().name(), "fraud_motor_test_symbol");
+        assert_eq!(found.offset(), 0);
+        assert_eq!(found.to_string(), "fraud_motor_test_symbol");
+
+        let found = symbols.lookup(addr + 1).unwrap();
+        assert_eq!(found.offset(), 1);
+        assert_eq!(found.to_string(), "fraud_motor_test_symbol+0x1");
+
+        let symbol = symbols.find("fraud_motor_test_symbol").unwrap();
+        assert_eq!(symbol.addr(), addr);
+        assert!(symbol.func());
+
+        let addr = rust_test_symbol as fn() -> u32 as usize;
+        let found = symbols.lookup(addr).unwrap();
+        assert!(found.symbol().raw_name().starts_with("_ZN"));
+        assert!(found.symbol().name().ends_with("tests::rust_test_symbol"));
+    }
+
+    #[test]
+    fn test_symbols_libc() {
+        let proc = Process::open(process::id()).unwrap();
+        let symbols = Symbols::load(&proc).unwrap();
+        let symbol = symbols.find("getpid").unwrap();
+        let getpid: extern "C" fn() -> u32 = unsafe { mem::transmute(symbol.addr()) };
+
+        assert!(symbol.module().starts_with("libc"));
+        assert_eq!(getpid(), process::id());
+    }
+
+    #[test]
+    fn test_symbols_nested() {
+        let symbol = |name: &str, addr, size| Symbol {
+            name: name.to_owned(),
+            demangled: None,
+            module: "test".to_owned(),
+            addr,
+            size,
+            func: true,
+        };
+        let symbols = Symbols::new(vec![
+            symbol("outer", 0x1000, 0x100),
+            symbol("inner", 0x1010, 0x10),
+            symbol("label", 0x1040, 0),
+            symbol("next", 0x1200, 0x10),
+        ]);
+        let name = |addr| symbols.lookup(addr).map(|found| found.to_string());
+
+        assert_eq!(name(0x1014).as_deref(), Some("inner+0x4"));
+        assert_eq!(name(0x1020).as_deref(), Some("outer+0x20"));
+        assert_eq!(name(0x1040).as_deref(), Some("label"));
+        assert_eq!(name(0x1041).as_deref(), Some("outer+0x41"));
+        assert_eq!(name(0x1100), None);
+        assert_eq!(name(0x1208).as_deref(), Some("next+0x8"));
+        assert_eq!(name(0xfff), None);
+    }
+
+    #[test]
+    fn test_demangle() {
+        assert_eq!(
+            demangle("_ZN4core3fmt5write17h0123456789abcdefE").as_deref(),
+            Some("core::fmt::write")
+        );
+        assert_eq!(
+            demangle("_ZN4game6Player5AddHPEi").as_deref(),
+            Some("game::Player::AddHP(int)")
+        );
+        assert_eq!(demangle("malloc"), None);
+    }
+}
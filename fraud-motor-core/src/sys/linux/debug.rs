@@ -0,0 +1,44 @@
+use crate::debug::{Breakpoint, Watchpoint};
+use crate::error::Result;
+use crate::sys::linux::trace::Tracer;
+use std::sync::Arc;
+
+pub struct Debugger(Arc<Tracer>);
+
+impl Debugger {
+    pub fn attach(id: u32) -> Result<Debugger> {
+        Tracer::attach(id).map(Debugger)
+    }
+
+    pub fn watch(&self, watchpoint: Watchpoint) -> Result<usize> {
+        self.0.run(move |tracee| tracee.set_watchpoint(watchpoint))
+    }
+
+    pub fn unwatch(&self, id: usize) -> Result<()> {
+        self.0.run(move |tracee| tracee.remove_watchpoint(id))
+    }
+
+    pub fn watchpoints(&self) -> Result<Vec<(usize, Watchpoint)>> {
+        self.0.run(|tracee| Ok(tracee.watchpoints()))
+    }
+
+    pub fn add_breakpoint(&self, addr: usize, auto_continue: bool) -> Result<()> {
+        self.0
+            .run(move |tracee| tracee.add_breakpoint(addr, auto_continue))
+    }
+
+    pub fn remove_breakpoint(&self, addr: usize) -> Result<()> {
+        self.0.run(move |tracee| tracee.remove_breakpoint(addr))
+    }
+
+    pub fn breakpoints(&self) -> Result<Vec<Breakpoint>> {
+        self.0.run(|tracee| Ok(tracee.breakpoints()))
+    }
+
+    pub fn resume(&self) -> Result<()> {
+        self.0.run(|tracee| {
+            tracee.resume_held();
+            Ok(())
+        })
+    }
+}
@@ -1,37 +1,241 @@
-use std::fs::{File, OpenOptions};
+use crate::error::{Error, Result};
+use crate::memory::Backend;
+use std::fs::{self, File, OpenOptions};
 use std::io;
 use std::os::unix::fs::FileExt;
 
-pub struct Memory(File);
+const IOV_MAX: usize = 1024;
 
-pub struct Options(OpenOptions);
+pub enum Memory {
+    File(File),
+    ProcessVm {
+        pid: libc::pid_t,
+        read: bool,
+        write: bool,
+    },
+}
+
+pub struct Options {
+    open: OpenOptions,
+    read: bool,
+    write: bool,
+    backend: Backend,
+}
 
 impl Memory {
-    pub fn open(id: u32, options: &Options) -> io::Result<Memory> {
-        options.0.open(format!("/proc/{}/mem", id)).map(Memory)
+    pub fn open(id: u32, options: &Options) -> Result<Memory> {
+        match options.backend {
+            Backend::ProcMem => {
+                let file = options
+                    .open
+                    .open(format!("/proc/{}/mem", id))
+                    .map_err(super::proc_error)?;
+
+                Ok(Memory::File(file))
+            }
+            Backend::ProcessVm => {
+                if !options.read && !options.write {
+                    return Err(io::Error::from_raw_os_error(libc::EINVAL).into());
+                }
+
+                fs::metadata(format!("/proc/{}", id)).map_err(super::proc_error)?;
+
+                Ok(Memory::ProcessVm {
+                    pid: id as libc::pid_t,
+                    read: options.read,
+                    write: options.write,
+                })
+            }
+        }
     }
 
-    pub fn read(&self, buf: &mut [u8], addr: usize) -> io::Result<()> {
-        self.0.read_exact_at(buf, addr as u64)
+    pub fn read(&self, buf: &mut [u8], addr: usize) -> Result<()> {
+        let mut read = 0;
+
+        while read < buf.len() {
+            let result = match self {
+                Memory::File(file) => file.read_at(&mut buf[read..], (addr + read) as u64),
+                Memory::ProcessVm { read: false, .. } => Err(not_permitted()),
+                Memory::ProcessVm { pid, .. } => {
+                    process_vm_readv(*pid, &mut [(addr + read, &mut buf[read..])])
+                }
+            };
+
+            match result {
+                Ok(0) if read == 0 => return Err(Error::ProcessGone),
+                Ok(0) => return Err(Error::PartialRead { addr, read }),
+                Ok(len) => read += len,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(_) if read > 0 => return Err(Error::PartialRead { addr, read }),
+                Err(err) => return Err(mem_error(err, addr)),
+            }
+        }
+
+        Ok(())
     }
 
-    pub fn write(&self, buf: &[u8], addr: usize) -> io::Result<()> {
-        self.0.write_all_at(buf, addr as u64)
+    pub fn read_many(&self, reads: &mut [(usize, &mut [u8])]) -> Vec<Result<()>> {
+        let pid = match self {
+            Memory::File(_) | Memory::ProcessVm { read: false, .. } => {
+                return reads
+                    .iter_mut()
+                    .map(|(addr, buf)| self.read(buf, *addr))
+                    .collect();
+            }
+            Memory::ProcessVm { pid, .. } => *pid,
+        };
+
+        let mut results = Vec::with_capacity(reads.len());
+
+        while results.len() < reads.len() {
+            let start = results.len();
+            let end = (start + IOV_MAX).min(reads.len());
+            let batch = &mut reads[start..end];
+
+            match process_vm_readv(pid, batch) {
+                Ok(mut len) => {
+                    for (addr, buf) in batch.iter() {
+                        if buf.len() <= len {
+                            len -= buf.len();
+                            results.push(Ok(()));
+                        } else {
+                            results.push(Err(match len {
+                                0 => Error::Unmapped(*addr),
+                                read => Error::PartialRead { addr: *addr, read },
+                            }));
+
+                            break;
+                        }
+                    }
+                }
+                Err(err) if err.raw_os_error() == Some(libc::ESRCH) => {
+                    results.resize_with(reads.len(), || Err(Error::ProcessGone));
+                }
+                Err(err) => results.push(Err(mem_error(err, batch[0].0))),
+            }
+        }
+
+        results
+    }
+
+    pub fn write(&self, buf: &[u8], addr: usize) -> Result<()> {
+        let mut written = 0;
+
+        while written < buf.len() {
+            let result = match self {
+                Memory::File(file) => file.write_at(&buf[written..], (addr + written) as u64),
+                Memory::ProcessVm { write: false, .. } => Err(not_permitted()),
+                Memory::ProcessVm { pid, .. } => {
+                    process_vm_writev(*pid, &buf[written..], addr + written)
+                }
+            };
+
+            match result {
+                Ok(0) => return Err(Error::ProcessGone),
+                Ok(len) => written += len,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+                Err(err) => return Err(mem_error(err, addr + written)),
+            }
+        }
+
+        Ok(())
     }
 }
 
 impl Options {
     pub fn new() -> Options {
-        Options(OpenOptions::new())
+        Options {
+            open: OpenOptions::new(),
+            read: false,
+            write: false,
+            backend: Backend::ProcMem,
+        }
     }
 
     pub fn read(&mut self, read: bool) -> &mut Options {
-        self.0.read(read);
+        self.open.read(read);
+        self.read = read;
         self
     }
 
     pub fn write(&mut self, write: bool) -> &mut Options {
-        self.0.write(write);
+        self.open.write(write);
+        self.write = write;
         self
     }
+
+    pub fn backend(&mut self, backend: Backend) -> &mut Options {
+        self.backend = backend;
+        self
+    }
+}
+
+pub fn page_size() -> usize {
+    unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+}
+
+fn process_vm_readv(pid: libc::pid_t, reads: &mut [(usize, &mut [u8])]) -> io::Result<usize> {
+    let local: Vec<_> = reads
+        .iter_mut()
+        .map(|(_, buf)| libc::iovec {
+            iov_base: buf.as_mut_ptr() as *mut libc::c_void,
+            iov_len: buf.len(),
+        })
+        .collect();
+
+    let remote: Vec<_> = reads
+        .iter()
+        .map(|(addr, buf)| libc::iovec {
+            iov_base: *addr as *mut libc::c_void,
+            iov_len: buf.len(),
+        })
+        .collect();
+
+    let len = unsafe {
+        libc::process_vm_readv(
+            pid,
+            local.as_ptr(),
+            local.len() as libc::c_ulong,
+            remote.as_ptr(),
+            remote.len() as libc::c_ulong,
+            0,
+        )
+    };
+
+    if len < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(len as usize)
+    }
+}
+
+fn process_vm_writev(pid: libc::pid_t, buf: &[u8], addr: usize) -> io::Result<usize> {
+    let local = libc::iovec {
+        iov_base: buf.as_ptr() as *mut libc::c_void,
+        iov_len: buf.len(),
+    };
+
+    let remote = libc::iovec {
+        iov_base: addr as *mut libc::c_void,
+        iov_len: buf.len(),
+    };
+
+    let len = unsafe { libc::process_vm_writev(pid, &local, 1, &remote, 1, 0) };
+
+    if len < 0 {
+        Err(io::Error::last_os_error())
+    } else {
+        Ok(len as usize)
+    }
+}
+
+fn not_permitted() -> io::Error {
+    io::Error::from_raw_os_error(libc::EBADF)
+}
+
+fn mem_error(err: io::Error, addr: usize) -> Error {
+    match err.raw_os_error() {
+        Some(libc::EIO | libc::EFAULT) => Error::Unmapped(addr),
+        _ => super::proc_error(err),
+    }
 }
@@ -1,2 +1,20 @@
+pub mod debug;
 pub mod memory;
 pub mod process;
+pub mod thread;
+
+#[cfg(target_arch = "x86_64")]
+mod trace;
+#[cfg(not(target_arch = "x86_64"))]
+#[path = "trace_unsupported.rs"]
+mod trace;
+
+use crate::error::Error;
+use std::io;
+
+fn proc_error(err: io::Error) -> Error {
+    match err.raw_os_error() {
+        Some(libc::ENOENT | libc::ESRCH) => Error::ProcessGone,
+        _ => err.into(),
+    }
+}
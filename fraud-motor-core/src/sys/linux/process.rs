@@ -1,7 +1,20 @@
+use crate::elf::Elf;
+use crate::error::{Error, Result};
+use crate::memory::page_size;
+use crate::process::{RegionKind, State};
+use crate::sys::linux::thread::Threads;
+use crate::sys::linux::trace::{Tracee, Tracer};
 use std::fs::{self, File, ReadDir};
 use std::io::{self, BufRead, BufReader, Lines};
 use std::marker::PhantomData;
+use std::ops::Range;
+use std::os::unix::ffi::OsStrExt;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+
+const SUSPEND_TIMEOUT: Duration = Duration::from_secs(5);
 
 pub struct List(ReadDir);
 
@@ -12,38 +25,70 @@ pub struct Regions<'a> {
     phantom: PhantomData<&'a Process>,
 }
 
+#[derive(Clone)]
 pub struct Region {
     start: usize,
     end: usize,
     permissions: Permissions,
-    path: Option<PathBuf>,
+    shared: bool,
+    offset: u64,
+    device: (u32, u32),
+    inode: u64,
+    kind: RegionKind,
+    name: Option<String>,
 }
 
+pub struct Suspended<'a> {
+    id: u32,
+    resume: bool,
+    tracer: Option<Arc<Tracer>>,
+    phantom: PhantomData<&'a Process>,
+}
+
+#[derive(Clone)]
 pub struct Permissions {
     read: bool,
     write: bool,
     exec: bool,
 }
 
+pub struct ProcessInfo {
+    id: u32,
+    name: String,
+    cmdline: Vec<String>,
+    parent_id: u32,
+    uid: u32,
+    state: State,
+    start_time: SystemTime,
+    rss: usize,
+}
+
 impl Iterator for List {
-    type Item = io::Result<u32>;
+    type Item = Result<u32>;
 
-    fn next(&mut self) -> Option<io::Result<u32>> {
+    fn next(&mut self) -> Option<Result<u32>> {
         self.0.find_map(|entry| {
             entry
                 .map(|entry| entry.file_name().to_str()?.parse().ok())
+                .map_err(Error::from)
                 .transpose()
         })
     }
 }
 
 impl Process {
-    pub fn open(id: u32) -> io::Result<Process> {
+    pub fn open(id: u32) -> Result<Process> {
+        fs::metadata(format!("/proc/{}", id)).map_err(super::proc_error)?;
+
         Ok(Process(id))
     }
 
-    pub fn regions(&self) -> io::Result<Regions> {
-        let file = File::open(format!("/proc/{}/maps", self.0))?;
+    pub fn id(&self) -> u32 {
+        self.0
+    }
+
+    pub fn regions(&self) -> Result<Regions<'_>> {
+        let file = File::open(format!("/proc/{}/maps", self.0)).map_err(super::proc_error)?;
 
         Ok(Regions {
             file: BufReader::new(file).lines(),
@@ -51,32 +96,276 @@ impl Process {
         })
     }
 
-    pub fn path(&self) -> io::Result<PathBuf> {
-        fs::read_link(format!("/proc/{}/exe", self.0))
+    pub fn path(&self) -> Result<PathBuf> {
+        fs::read_link(format!("/proc/{}/exe", self.0)).map_err(super::proc_error)
+    }
+
+    pub fn suspend(&self) -> Result<Suspended<'_>> {
+        if let Some(tracer) = Tracer::find(self.0) {
+            tracer.run(|tracee| {
+                tracee.suspend();
+                Ok(())
+            })?;
+
+            return Ok(Suspended {
+                id: self.0,
+                resume: false,
+                tracer: Some(tracer),
+                phantom: PhantomData,
+            });
+        }
+
+        let resume = !all_stopped(self)?;
+        let suspended = Suspended {
+            id: self.0,
+            resume,
+            tracer: None,
+            phantom: PhantomData,
+        };
+
+        if resume {
+            let deadline = Instant::now() + SUSPEND_TIMEOUT;
+
+            signal(self.0, libc::SIGSTOP)?;
+
+            while !all_stopped(self)? {
+                if Instant::now() >= deadline {
+                    return Err(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        "timed out waiting for the process to stop",
+                    )
+                    .into());
+                }
+
+                thread::sleep(Duration::from_millis(1));
+            }
+        }
+
+        Ok(suspended)
+    }
+
+    pub fn threads(&self) -> Result<Threads<'_>> {
+        Threads::new(self.0)
+    }
+
+    pub fn alloc(
+        &self,
+        addr: Option<usize>,
+        size: usize,
+        permissions: &Permissions,
+    ) -> Result<usize> {
+        let fixed = match addr {
+            Some(_) => libc::MAP_FIXED_NOREPLACE,
+            None => 0,
+        };
+
+        let args = [
+            addr.unwrap_or(0) as u64,
+            size as u64,
+            permissions.prot() as u64,
+            (libc::MAP_PRIVATE | libc::MAP_ANONYMOUS | fixed) as u64,
+            u64::MAX,
+            0,
+        ];
+
+        Tracer::attach(self.0)?.run(move |tracee| {
+            let mapped = tracee.syscall(libc::SYS_mmap, args)? as usize;
+
+            match addr {
+                Some(addr) if addr != mapped => {
+                    tracee.syscall(libc::SYS_munmap, [mapped as u64, size as u64, 0, 0, 0, 0])?;
+
+                    Err(io::Error::from_raw_os_error(libc::EEXIST).into())
+                }
+                _ => Ok(mapped),
+            }
+        })
+    }
+
+    pub fn free(&self, addr: usize, size: usize) -> Result<()> {
+        let args = [addr as u64, size as u64, 0, 0, 0, 0];
+
+        Tracer::attach(self.0)?
+            .run(move |tracee| tracee.syscall(libc::SYS_munmap, args))
+            .map(|_| ())
+    }
+
+    pub fn protect(&self, addr: usize, size: usize, permissions: &Permissions) -> Result<()> {
+        let args = [addr as u64, size as u64, permissions.prot() as u64, 0, 0, 0];
+
+        Tracer::attach(self.0)?
+            .run(move |tracee| tracee.syscall(libc::SYS_mprotect, args))
+            .map(|_| ())
+    }
+
+    pub fn write_stopped(&self, buf: &[u8], addr: usize, busy: &[Range<usize>]) -> Result<()> {
+        let buf = buf.to_vec();
+        let busy = busy.to_vec();
+
+        Tracer::attach(self.0)?.run(move |tracee| tracee.write_stopped(&buf, addr, &busy))
+    }
+
+    pub fn call(&self, addr: usize, args: &[u64]) -> Result<u64> {
+        let args = args.to_vec();
+
+        Tracer::attach(self.0)?.run(move |tracee| tracee.call(addr, &args))
+    }
+
+    pub fn inject_library(&self, path: &Path) -> Result<usize> {
+        let page_size = page_size();
+        let dlopen = self.libc_export(&["dlopen", "__libc_dlopen_mode"])?;
+        let dlerror = self.libc_export(&["dlerror"]).ok();
+        let mut name = path.as_os_str().as_bytes().to_vec();
+
+        name.push(0);
+
+        Tracer::attach(self.0)?.run(move |tracee| {
+            let size = name.len().div_ceil(page_size) * page_size;
+            let args = [
+                0,
+                size as u64,
+                (libc::PROT_READ | libc::PROT_WRITE) as u64,
+                (libc::MAP_PRIVATE | libc::MAP_ANONYMOUS) as u64,
+                u64::MAX,
+                0,
+            ];
+
+            let addr = tracee.syscall(libc::SYS_mmap, args)? as usize;
+            let handle = tracee
+                .write_memory(&name, addr)
+                .and_then(|_| tracee.call(dlopen, &[addr as u64, libc::RTLD_NOW as u64]));
+
+            tracee.syscall(libc::SYS_munmap, [addr as u64, size as u64, 0, 0, 0, 0])?;
+
+            match handle? {
+                0 => Err(dl_error(tracee, dlerror, "dlopen failed")),
+                handle => Ok(handle as usize),
+            }
+        })
+    }
+
+    pub fn unload_library(&self, handle: usize) -> Result<()> {
+        let dlclose = self.libc_export(&["dlclose", "__libc_dlclose"])?;
+        let dlerror = self.libc_export(&["dlerror"]).ok();
+
+        Tracer::attach(self.0)?.run(
+            move |tracee| match tracee.call(dlclose, &[handle as u64])? {
+                0 => Ok(()),
+                _ => Err(dl_error(tracee, dlerror, "dlclose failed")),
+            },
+        )
+    }
+
+    fn libc_export(&self, names: &[&str]) -> Result<usize> {
+        let mut libc = None;
+
+        for region in self.regions()? {
+            let region = region?;
+            let Some(path) = region.path() else {
+                continue;
+            };
+
+            let name = path.file_name().and_then(|name| name.to_str());
+
+            if name.is_some_and(|name| name.starts_with("libc.so") || name.starts_with("libc-")) {
+                libc = Some((region.start(), path.to_owned()));
+                break;
+            }
+        }
+
+        let (base, path) =
+            libc.ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "libc not loaded"))?;
+        let elf = Elf::open(&path)?;
+        let bias = base.wrapping_sub(elf.base() as usize);
+
+        names
+            .iter()
+            .find_map(|&name| {
+                elf.symbols()
+                    .iter()
+                    .find(|symbol| symbol.dynamic() && symbol.func() && symbol.name() == name)
+            })
+            .map(|symbol| bias.wrapping_add(symbol.value() as usize))
+            .ok_or_else(|| {
+                let msg = format!("{} not exported by libc", names[0]);
+
+                io::Error::new(io::ErrorKind::NotFound, msg).into()
+            })
+    }
+
+    pub fn info(&self) -> Result<ProcessInfo> {
+        let read = |name| fs::read(format!("/proc/{}/{}", self.0, name));
+        let stat = read("stat").map_err(super::proc_error)?;
+        let cmdline = read("cmdline").map_err(super::proc_error)?;
+        let status = read("status").map_err(super::proc_error)?;
+        let stat = String::from_utf8_lossy(&stat);
+        let status = String::from_utf8_lossy(&status);
+
+        let (_, stat) = stat.rsplit_once(')').ok_or_else(|| invalid_data(&stat))?;
+        let stat: Vec<_> = stat.split_whitespace().collect();
+        let start_ticks: u64 = stat
+            .get(19)
+            .and_then(|s| s.parse().ok())
+            .ok_or_else(|| invalid_data(&stat.join(" ")))?;
+
+        let cmdline = cmdline
+            .split(|&byte| byte == 0)
+            .filter(|arg| !arg.is_empty())
+            .map(|arg| String::from_utf8_lossy(arg).into_owned())
+            .collect();
+
+        let field = |name: &str| {
+            status
+                .lines()
+                .find_map(|line| line.strip_prefix(name)?.strip_prefix(':'))
+                .map(str::trim)
+                .ok_or_else(|| invalid_data(name))
+        };
+
+        let number = |name: &str| {
+            field(name)?
+                .split_whitespace()
+                .next()
+                .and_then(|value| value.parse().ok())
+                .ok_or_else(|| invalid_data(name))
+        };
+
+        let state = parse_state(field("State")?);
+
+        Ok(ProcessInfo {
+            id: self.0,
+            name: field("Name")?.to_owned(),
+            cmdline,
+            parent_id: number("PPid")? as u32,
+            uid: number("Uid")? as u32,
+            state,
+            start_time: boot_time()? + Duration::from_secs_f64(start_ticks as f64 / clock_ticks()),
+            rss: number("VmRSS").unwrap_or(0) as usize * 1024,
+        })
+    }
+}
+
+impl<'a> Drop for Suspended<'a> {
+    fn drop(&mut self) {
+        if let Some(tracer) = &self.tracer {
+            let _ = tracer.run(|tracee| {
+                tracee.unsuspend();
+                Ok(())
+            });
+        } else if self.resume {
+            let _ = signal(self.id, libc::SIGCONT);
+        }
     }
 }
 
 impl<'a> Iterator for Regions<'a> {
-    type Item = io::Result<Region>;
+    type Item = Result<Region>;
 
-    fn next(&mut self) -> Option<io::Result<Region>> {
+    fn next(&mut self) -> Option<Result<Region>> {
         self.file.next().map(|line| {
-            let line = line?;
-            let mut line = line.split_whitespace();
-            let (start, end) = line.next().unwrap().split_once('-').unwrap();
-            let permissions = line.next().unwrap().as_bytes();
-            let path = line.nth(3).filter(|line| line.starts_with('/'));
-
-            Ok(Region {
-                start: usize::from_str_radix(start, 16).unwrap(),
-                end: usize::from_str_radix(end, 16).unwrap(),
-                permissions: Permissions {
-                    read: permissions[0] == b'r',
-                    write: permissions[1] == b'w',
-                    exec: permissions[2] == b'x',
-                },
-                path: path.map(Into::into),
-            })
+            let line = line.map_err(super::proc_error)?;
+
+            parse_region(&line).ok_or_else(|| invalid_data(&line).into())
         })
     }
 }
@@ -94,12 +383,46 @@ impl Region {
         Permissions { ..self.permissions }
     }
 
+    pub fn shared(&self) -> bool {
+        self.shared
+    }
+
+    pub fn offset(&self) -> u64 {
+        self.offset
+    }
+
+    pub fn device(&self) -> (u32, u32) {
+        self.device
+    }
+
+    pub fn inode(&self) -> u64 {
+        self.inode
+    }
+
+    pub fn kind(&self) -> RegionKind {
+        self.kind
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
     pub fn path(&self) -> Option<&Path> {
-        self.path.as_deref()
+        let name = self.name.as_deref()?;
+
+        match self.kind {
+            RegionKind::File => Some(Path::new(name)),
+            RegionKind::DeletedFile => name.strip_suffix(" (deleted)").map(Path::new),
+            _ => None,
+        }
     }
 }
 
 impl Permissions {
+    pub fn new(read: bool, write: bool, exec: bool) -> Permissions {
+        Permissions { read, write, exec }
+    }
+
     pub fn read(&self) -> bool {
         self.read
     }
@@ -111,8 +434,183 @@ impl Permissions {
     pub fn exec(&self) -> bool {
         self.exec
     }
+
+    fn prot(&self) -> libc::c_int {
+        let mut prot = libc::PROT_NONE;
+
+        if self.read {
+            prot |= libc::PROT_READ;
+        }
+
+        if self.write {
+            prot |= libc::PROT_WRITE;
+        }
+
+        if self.exec {
+            prot |= libc::PROT_EXEC;
+        }
+
+        prot
+    }
+}
+
+impl ProcessInfo {
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn cmdline(&self) -> &[String] {
+        &self.cmdline
+    }
+
+    pub fn parent_id(&self) -> u32 {
+        self.parent_id
+    }
+
+    pub fn uid(&self) -> u32 {
+        self.uid
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn start_time(&self) -> SystemTime {
+        self.start_time
+    }
+
+    pub fn rss(&self) -> usize {
+        self.rss
+    }
+}
+
+fn parse_region(line: &str) -> Option<Region> {
+    let mut fields = line.splitn(6, ' ');
+    let (start, end) = fields.next()?.split_once('-')?;
+    let &[read, write, exec, shared] = fields.next()?.as_bytes() else {
+        return None;
+    };
+    let offset = fields.next()?;
+    let (major, minor) = fields.next()?.split_once(':')?;
+    let inode = fields.next()?;
+    let name = fields
+        .next()
+        .map(str::trim_start)
+        .filter(|name| !name.is_empty());
+
+    let kind = match name {
+        None => RegionKind::Anonymous,
+        Some("[heap]") => RegionKind::Heap,
+        Some("[vdso]") => RegionKind::Vdso,
+        Some("[vvar]") => RegionKind::Vvar,
+        Some(name) if name.starts_with("[stack") => RegionKind::Stack,
+        Some(name) if name.starts_with("[anon:") || name.starts_with("[anon_shmem:") => {
+            RegionKind::NamedAnonymous
+        }
+        Some(name) if name.ends_with(" (deleted)") => RegionKind::DeletedFile,
+        Some(name) if name.starts_with('/') => RegionKind::File,
+        Some(_) => RegionKind::Other,
+    };
+
+    Some(Region {
+        start: usize::from_str_radix(start, 16).ok()?,
+        end: usize::from_str_radix(end, 16).ok()?,
+        permissions: Permissions {
+            read: read == b'r',
+            write: write == b'w',
+            exec: exec == b'x',
+        },
+        shared: shared == b's',
+        offset: u64::from_str_radix(offset, 16).ok()?,
+        device: (
+            u32::from_str_radix(major, 16).ok()?,
+            u32::from_str_radix(minor, 16).ok()?,
+        ),
+        inode: inode.parse().ok()?,
+        kind,
+        name: name.map(Into::into),
+    })
+}
+
+pub fn parse_state(state: &str) -> State {
+    match state.trim_start().chars().next() {
+        Some('R') => State::Running,
+        Some('S') => State::Sleeping,
+        Some('D') => State::Waiting,
+        Some('Z') => State::Zombie,
+        Some('T') => State::Stopped,
+        Some('t') => State::Traced,
+        Some('X') => State::Dead,
+        Some('I') => State::Idle,
+        _ => State::Unknown,
+    }
+}
+
+pub fn invalid_data(data: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, data)
+}
+
+fn signal(id: u32, signal: libc::c_int) -> Result<()> {
+    if unsafe { libc::kill(id as libc::pid_t, signal) } < 0 {
+        Err(super::proc_error(io::Error::last_os_error()))
+    } else {
+        Ok(())
+    }
+}
+
+fn all_stopped(process: &Process) -> Result<bool> {
+    for thread in process.threads()? {
+        if !matches!(
+            thread?.state(),
+            State::Stopped | State::Traced | State::Zombie | State::Dead
+        ) {
+            return Ok(false);
+        }
+    }
+
+    Ok(true)
+}
+
+fn dl_error(tracee: &mut Tracee, dlerror: Option<usize>, default: &str) -> Error {
+    let message = dlerror
+        .and_then(|dlerror| tracee.call(dlerror, &[]).ok())
+        .filter(|&addr| addr != 0)
+        .and_then(|addr| {
+            let mut message = Vec::new();
+
+            for addr in addr as usize.. {
+                let mut byte = [0];
+
+                match tracee.read_memory(&mut byte, addr) {
+                    Ok(()) if byte[0] != 0 && message.len() < page_size() => message.push(byte[0]),
+                    Ok(()) => break,
+                    Err(_) => return None,
+                }
+            }
+
+            Some(String::from_utf8_lossy(&message).into_owned())
+        });
+
+    io::Error::other(message.unwrap_or_else(|| default.to_owned())).into()
+}
+
+fn boot_time() -> Result<SystemTime> {
+    let stat = fs::read_to_string("/proc/stat")?;
+
+    stat.lines()
+        .find_map(|line| line.strip_prefix("btime ")?.trim().parse().ok())
+        .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs))
+        .ok_or_else(|| invalid_data("btime").into())
+}
+
+fn clock_ticks() -> f64 {
+    unsafe { libc::sysconf(libc::_SC_CLK_TCK) as f64 }
 }
 
-pub fn list() -> io::Result<List> {
-    fs::read_dir("/proc").map(List)
+pub fn list() -> Result<List> {
+    Ok(List(fs::read_dir("/proc")?))
 }
@@ -0,0 +1,142 @@
+use crate::error::{Error, Result};
+use crate::process::State;
+use crate::sys::linux::process::{self, Process};
+use crate::sys::linux::trace::Tracer;
+use crate::thread::Registers;
+use std::fs::{self, ReadDir};
+use std::io;
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+pub struct Threads<'a> {
+    id: u32,
+    dir: ReadDir,
+    tracer: Option<Option<Arc<Tracer>>>,
+    phantom: PhantomData<&'a Process>,
+}
+
+pub struct Thread {
+    process: u32,
+    id: u32,
+    name: String,
+    state: State,
+    stack_pointer: Option<usize>,
+}
+
+impl<'a> Threads<'a> {
+    pub fn new(id: u32) -> Result<Threads<'a>> {
+        Ok(Threads {
+            id,
+            dir: fs::read_dir(format!("/proc/{}/task", id)).map_err(super::proc_error)?,
+            tracer: None,
+            phantom: PhantomData,
+        })
+    }
+
+    fn stack_pointer(&mut self, id: u32) -> Option<usize> {
+        let process = self.id;
+        let tracer = self
+            .tracer
+            .get_or_insert_with(|| Tracer::attach(process).ok())
+            .as_ref()?;
+        let id = id as libc::pid_t;
+
+        tracer
+            .run(move |tracee| tracee.registers(id))
+            .ok()
+            .map(|registers| registers.rsp as usize)
+    }
+}
+
+impl<'a> Iterator for Threads<'a> {
+    type Item = Result<Thread>;
+
+    fn next(&mut self) -> Option<Result<Thread>> {
+        for entry in self.dir.by_ref() {
+            let id = match entry.map(|entry| entry.file_name().to_str()?.parse().ok()) {
+                Ok(Some(id)) => id,
+                Ok(None) => continue,
+                Err(err) => return Some(Err(super::proc_error(err))),
+            };
+
+            match Thread::open(self.id, id) {
+                Err(Error::ProcessGone) => continue,
+                Ok(mut thread) => {
+                    if thread.stack_pointer.is_none() {
+                        thread.stack_pointer = self.stack_pointer(id);
+                    }
+
+                    return Some(Ok(thread));
+                }
+                Err(err) => return Some(Err(err)),
+            }
+        }
+
+        None
+    }
+}
+
+impl Thread {
+    fn open(process: u32, id: u32) -> Result<Thread> {
+        let read = |name| {
+            fs::read(format!("/proc/{}/task/{}/{}", process, id, name))
+                .map(|data| String::from_utf8_lossy(&data).into_owned())
+                .map_err(super::proc_error)
+        };
+
+        let name = read("comm")?.trim_end_matches('\n').to_owned();
+        let stat = read("stat")?;
+        let (_, state) = stat
+            .rsplit_once(')')
+            .ok_or_else(|| process::invalid_data(&stat))?;
+
+        let stack_pointer = read("syscall").ok().and_then(|syscall| {
+            let fields: Vec<_> = syscall.split_whitespace().collect();
+            let sp = fields.get(fields.len().checked_sub(2)?)?;
+
+            usize::from_str_radix(sp.strip_prefix("0x")?, 16).ok()
+        });
+
+        Ok(Thread {
+            process,
+            id,
+            name,
+            state: process::parse_state(state),
+            stack_pointer,
+        })
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn state(&self) -> State {
+        self.state
+    }
+
+    pub fn stack_pointer(&self) -> Option<usize> {
+        self.stack_pointer
+    }
+
+    pub fn registers(&self) -> Result<Registers> {
+        let id = self.id as libc::pid_t;
+
+        Tracer::attach(self.process)?.run(move |tracee| tracee.registers(id))
+    }
+
+    pub fn set_registers(&self, registers: &Registers) -> Result<()> {
+        let id = self.id as libc::pid_t;
+        let registers = *registers;
+
+        Tracer::attach(self.process)?.run(move |tracee| tracee.set_registers(id, &registers))
+    }
+}
+
+#[cfg_attr(not(target_arch = "x86_64"), allow(dead_code))]
+pub fn thread_gone() -> Error {
+    io::Error::new(io::ErrorKind::NotFound, "thread not found").into()
+}
@@ -0,0 +1,963 @@
+use crate::debug::{Breakpoint, WatchKind, Watchpoint};
+use crate::error::{Error, Result};
+use crate::sys::linux::memory;
+use crate::sys::linux::process::Process;
+use crate::sys::linux::thread;
+use crate::thread::Registers;
+use iced_x86::{Decoder, DecoderOptions, OpKind, Register};
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::ops::Range;
+use std::os::unix::fs::FileExt;
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::sync::{Arc, Mutex, Weak};
+use std::thread::JoinHandle;
+use std::time::Duration;
+use std::{fs, io, mem};
+
+const OPTIONS: libc::c_int = libc::PTRACE_O_TRACECLONE;
+const DEBUG_REGISTERS: usize = 4;
+const INT3: u8 = 0xcc;
+const SYSCALL: [u8; 2] = [0x0f, 0x05];
+const RIP: usize = mem::offset_of!(libc::user_regs_struct, rip);
+const RED_ZONE: u64 = 128;
+const MAX_INSTRUCTION_SIZE: usize = 15;
+// Remote calls return to an unmapped address so that the fault marks the end
+// of the call, whatever code the callee runs on the way.
+const CALL_RETURN: u64 = 0;
+
+type Command = Box<dyn FnOnce(&mut Tracee) + Send>;
+
+pub struct Tracer {
+    sender: Option<Sender<Command>>,
+    thread: Option<JoinHandle<()>>,
+}
+
+pub struct Tracee {
+    id: u32,
+    memory: File,
+    tasks: BTreeMap<libc::pid_t, Task>,
+    watchpoints: [Option<Watchpoint>; DEBUG_REGISTERS],
+    breakpoints: BTreeMap<usize, Breakpoint>,
+    suspended: usize,
+}
+
+#[derive(Default)]
+struct Task {
+    starting: bool,
+    stopped: bool,
+    listening: bool,
+    held: bool,
+    signal: libc::c_int,
+}
+
+static TRACERS: Mutex<Vec<(u32, Weak<Tracer>)>> = Mutex::new(Vec::new());
+
+impl Tracer {
+    pub fn attach(id: u32) -> Result<Arc<Tracer>> {
+        let mut tracers = TRACERS.lock().unwrap();
+
+        if let Some(tracer) = find(&mut tracers, id) {
+            return Ok(tracer);
+        }
+
+        let (sender, receiver) = mpsc::channel::<Command>();
+        let (ready_sender, ready) = mpsc::channel();
+
+        let thread = std::thread::spawn(move || {
+            let mut tracee = match Tracee::seize(id) {
+                Ok(tracee) => {
+                    let _ = ready_sender.send(Ok(()));
+                    tracee
+                }
+                Err(err) => {
+                    let _ = ready_sender.send(Err(err));
+                    return;
+                }
+            };
+
+            loop {
+                match receiver.recv_timeout(Duration::from_millis(1)) {
+                    Ok(command) => {
+                        tracee.stop_all();
+                        command(&mut tracee);
+
+                        if tracee.suspended == 0 {
+                            tracee.resume_all();
+                        }
+                    }
+                    Err(RecvTimeoutError::Timeout) => {}
+                    Err(RecvTimeoutError::Disconnected) => break,
+                }
+
+                tracee.poll();
+            }
+
+            tracee.detach();
+        });
+
+        ready.recv().map_err(|_| Error::ProcessGone)??;
+
+        let tracer = Arc::new(Tracer {
+            sender: Some(sender),
+            thread: Some(thread),
+        });
+
+        tracers.push((id, Arc::downgrade(&tracer)));
+        Ok(tracer)
+    }
+
+    pub fn find(id: u32) -> Option<Arc<Tracer>> {
+        find(&mut TRACERS.lock().unwrap(), id)
+    }
+
+    pub fn run<R, F>(&self, command: F) -> Result<R>
+    where
+        R: Send + 'static,
+        F: FnOnce(&mut Tracee) -> Result<R> + Send + 'static,
+    {
+        let (sender, receiver) = mpsc::channel();
+
+        self.sender
+            .as_ref()
+            .unwrap()
+            .send(Box::new(move |tracee| {
+                let _ = sender.send(command(tracee));
+            }))
+            .map_err(|_| Error::ProcessGone)?;
+
+        receiver.recv().map_err(|_| Error::ProcessGone)?
+    }
+}
+
+impl Drop for Tracer {
+    fn drop(&mut self) {
+        drop(self.sender.take());
+
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
+
+impl Tracee {
+    fn seize(id: u32) -> Result<Tracee> {
+        let memory = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(format!("/proc/{}/mem", id))
+            .map_err(super::proc_error)?;
+
+        let mut tracee = Tracee {
+            id,
+            memory,
+            tasks: BTreeMap::new(),
+            watchpoints: Default::default(),
+            breakpoints: BTreeMap::new(),
+            suspended: 0,
+        };
+
+        loop {
+            let mut seized = false;
+
+            for entry in fs::read_dir(format!("/proc/{}/task", id)).map_err(super::proc_error)? {
+                let tid = match entry?.file_name().to_str().and_then(|s| s.parse().ok()) {
+                    Some(tid) if !tracee.tasks.contains_key(&tid) => tid,
+                    _ => continue,
+                };
+
+                match ptrace(libc::PTRACE_SEIZE, tid, 0, OPTIONS as usize) {
+                    Ok(_) => {
+                        tracee.tasks.insert(tid, Task::default());
+                        seized = true;
+                    }
+                    Err(err) if err.raw_os_error() == Some(libc::ESRCH) => {}
+                    Err(err) => {
+                        tracee.detach();
+                        return Err(err.into());
+                    }
+                }
+            }
+
+            if !seized {
+                break;
+            }
+        }
+
+        if tracee.tasks.is_empty() {
+            Err(Error::ProcessGone)
+        } else {
+            Ok(tracee)
+        }
+    }
+
+    pub fn registers(&self, tid: libc::pid_t) -> Result<Registers> {
+        self.task(tid)?;
+
+        let regs = get_regs(tid).map_err(super::proc_error)?;
+
+        Ok(Registers {
+            rax: regs.rax,
+            rbx: regs.rbx,
+            rcx: regs.rcx,
+            rdx: regs.rdx,
+            rsi: regs.rsi,
+            rdi: regs.rdi,
+            rbp: regs.rbp,
+            rsp: regs.rsp,
+            r8: regs.r8,
+            r9: regs.r9,
+            r10: regs.r10,
+            r11: regs.r11,
+            r12: regs.r12,
+            r13: regs.r13,
+            r14: regs.r14,
+            r15: regs.r15,
+            rip: regs.rip,
+            rflags: regs.eflags,
+            fs_base: regs.fs_base,
+            gs_base: regs.gs_base,
+        })
+    }
+
+    pub fn set_registers(&self, tid: libc::pid_t, registers: &Registers) -> Result<()> {
+        self.task(tid)?;
+
+        let mut regs = get_regs(tid).map_err(super::proc_error)?;
+
+        regs.rax = registers.rax;
+        regs.rbx = registers.rbx;
+        regs.rcx = registers.rcx;
+        regs.rdx = registers.rdx;
+        regs.rsi = registers.rsi;
+        regs.rdi = registers.rdi;
+        regs.rbp = registers.rbp;
+        regs.rsp = registers.rsp;
+        regs.r8 = registers.r8;
+        regs.r9 = registers.r9;
+        regs.r10 = registers.r10;
+        regs.r11 = registers.r11;
+        regs.r12 = registers.r12;
+        regs.r13 = registers.r13;
+        regs.r14 = registers.r14;
+        regs.r15 = registers.r15;
+        regs.rip = registers.rip;
+        regs.eflags = registers.rflags;
+        regs.fs_base = registers.fs_base;
+        regs.gs_base = registers.gs_base;
+
+        set_regs(tid, &regs).map_err(super::proc_error)
+    }
+
+    pub fn syscall(&mut self, nr: libc::c_long, args: [u64; 6]) -> Result<u64> {
+        let tid = self.thread()?;
+        let saved = get_regs(tid).map_err(super::proc_error)?;
+        let addr = saved.rip as usize;
+        let mut original = [0; SYSCALL.len()];
+        let mut regs = saved;
+
+        regs.rax = nr as u64;
+        regs.orig_rax = u64::MAX;
+        regs.rdi = args[0];
+        regs.rsi = args[1];
+        regs.rdx = args[2];
+        regs.r10 = args[3];
+        regs.r8 = args[4];
+        regs.r9 = args[5];
+
+        self.read_memory(&mut original, addr)?;
+        self.write_memory(&SYSCALL, addr)?;
+
+        let result = set_regs(tid, &regs)
+            .map_err(super::proc_error)
+            .and_then(|_| match self.single_step(tid) {
+                true => get_regs(tid).map_err(super::proc_error),
+                false => Err(Error::ProcessGone),
+            });
+
+        let _ = self.memory.write_all_at(&original, addr as u64);
+        let _ = set_regs(tid, &saved);
+
+        match result?.rax as i64 {
+            err @ -4095..=-1 => Err(io::Error::from_raw_os_error(-err as i32).into()),
+            value => Ok(value as u64),
+        }
+    }
+
+    pub fn call(&mut self, addr: usize, args: &[u64]) -> Result<u64> {
+        let tid = self.thread()?;
+        let saved = get_regs(tid).map_err(super::proc_error)?;
+        let stack = args.get(6..).unwrap_or_default();
+        let mut regs = saved;
+        let mut sp = (saved.rsp - RED_ZONE) & !0xf;
+
+        if stack.len() % 2 == 1 {
+            sp -= 8;
+        }
+
+        for &arg in stack.iter().rev() {
+            sp -= 8;
+            self.write_memory(&arg.to_ne_bytes(), sp as usize)?;
+        }
+
+        sp -= 8;
+        self.write_memory(&CALL_RETURN.to_ne_bytes(), sp as usize)?;
+
+        for (reg, &arg) in [
+            &mut regs.rdi,
+            &mut regs.rsi,
+            &mut regs.rdx,
+            &mut regs.rcx,
+            &mut regs.r8,
+            &mut regs.r9,
+        ]
+        .into_iter()
+        .zip(args)
+        {
+            *reg = arg;
+        }
+
+        regs.rax = 0;
+        regs.orig_rax = u64::MAX;
+        regs.rsp = sp;
+        regs.rip = addr as u64;
+
+        let result = set_regs(tid, &regs)
+            .map_err(super::proc_error)
+            .and_then(|_| self.call_wait(tid, sp + 8));
+
+        let _ = set_regs(tid, &saved);
+
+        result
+    }
+
+    fn call_wait(&mut self, tid: libc::pid_t, ret_sp: u64) -> Result<u64> {
+        while ptrace(libc::PTRACE_CONT, tid, 0, 0).is_ok() {
+            let status = match wait(tid, 0) {
+                Ok(Some(status)) if libc::WIFSTOPPED(status) => status,
+                _ => break,
+            };
+
+            let signal = libc::WSTOPSIG(status);
+
+            if status >> 16 != 0 {
+                self.event(tid, status);
+            } else if signal == libc::SIGTRAP {
+                self.trap(tid);
+
+                if let Some(task) = self.tasks.get_mut(&tid).filter(|task| task.held) {
+                    task.held = false;
+                    self.step_over(tid);
+                }
+            } else if matches!(
+                signal,
+                libc::SIGSEGV | libc::SIGBUS | libc::SIGILL | libc::SIGFPE
+            ) {
+                let regs = get_regs(tid).map_err(super::proc_error)?;
+
+                if signal == libc::SIGSEGV && regs.rip == CALL_RETURN && regs.rsp == ret_sp {
+                    return Ok(regs.rax);
+                }
+
+                return Err(io::Error::other(format!(
+                    "remote call interrupted by signal {}",
+                    signal
+                ))
+                .into());
+            } else if let Some(task) = self.tasks.get_mut(&tid) {
+                task.signal = signal;
+            }
+        }
+
+        self.tasks.remove(&tid);
+        Err(Error::ProcessGone)
+    }
+
+    pub fn suspend(&mut self) {
+        self.suspended += 1;
+    }
+
+    pub fn unsuspend(&mut self) {
+        self.suspended = self.suspended.saturating_sub(1);
+    }
+
+    pub fn read_memory(&self, buf: &mut [u8], addr: usize) -> Result<()> {
+        self.memory
+            .read_exact_at(buf, addr as u64)
+            .map_err(|_| Error::Unmapped(addr))
+    }
+
+    pub fn write_memory(&self, buf: &[u8], addr: usize) -> Result<()> {
+        self.memory
+            .write_all_at(buf, addr as u64)
+            .map_err(|_| Error::Unmapped(addr))
+    }
+
+    pub fn write_stopped(&self, buf: &[u8], addr: usize, busy: &[Range<usize>]) -> Result<()> {
+        for &tid in self.tasks.keys() {
+            let ip = peek_user(tid, RIP).map_err(super::proc_error)?;
+
+            if busy.iter().any(|range| range.contains(&ip)) {
+                return Err(io::Error::new(
+                    io::ErrorKind::ResourceBusy,
+                    "a thread is executing the code being replaced",
+                )
+                .into());
+            }
+        }
+
+        self.write_memory(buf, addr)
+    }
+
+    fn thread(&self) -> Result<libc::pid_t> {
+        let main = self.id as libc::pid_t;
+
+        if self.tasks.contains_key(&main) {
+            Ok(main)
+        } else {
+            self.tasks.keys().next().copied().ok_or(Error::ProcessGone)
+        }
+    }
+
+    pub fn set_watchpoint(&mut self, watchpoint: Watchpoint) -> Result<usize> {
+        let id = self
+            .watchpoints
+            .iter()
+            .position(Option::is_none)
+            .ok_or_else(|| io::Error::other("no free debug register"))?;
+
+        self.watchpoints[id] = Some(watchpoint);
+
+        if let Err(err) = self.update_debug_registers() {
+            self.watchpoints[id] = None;
+            let _ = self.update_debug_registers();
+
+            return Err(err);
+        }
+
+        Ok(id)
+    }
+
+    pub fn remove_watchpoint(&mut self, id: usize) -> Result<()> {
+        self.watchpoints
+            .get_mut(id)
+            .and_then(Option::take)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "watchpoint not found"))?;
+
+        self.update_debug_registers()
+    }
+
+    pub fn watchpoints(&self) -> Vec<(usize, Watchpoint)> {
+        self.watchpoints
+            .iter()
+            .enumerate()
+            .filter_map(|(id, watchpoint)| Some((id, watchpoint.clone()?)))
+            .collect()
+    }
+
+    fn update_debug_registers(&self) -> Result<()> {
+        for &tid in self.tasks.keys() {
+            self.set_debug_registers(tid).map_err(super::proc_error)?;
+        }
+
+        Ok(())
+    }
+
+    fn set_debug_registers(&self, tid: libc::pid_t) -> io::Result<()> {
+        let mut dr7 = 0;
+
+        for (i, watchpoint) in self.watchpoints.iter().enumerate() {
+            if let Some(watchpoint) = watchpoint {
+                let rw = match watchpoint.kind() {
+                    WatchKind::Write => 0b01,
+                    WatchKind::Access => 0b11,
+                };
+
+                let len = match watchpoint.size() {
+                    1 => 0b00,
+                    2 => 0b01,
+                    8 => 0b10,
+                    _ => 0b11,
+                };
+
+                poke_user(tid, debug_register(i), watchpoint.addr())?;
+                dr7 |= 1 << (i * 2) | rw << (16 + i * 4) | len << (18 + i * 4);
+            }
+        }
+
+        poke_user(tid, debug_register(7), dr7)
+    }
+
+    pub fn add_breakpoint(&mut self, addr: usize, auto_continue: bool) -> Result<()> {
+        if self.breakpoints.contains_key(&addr) {
+            return Err(
+                io::Error::new(io::ErrorKind::AlreadyExists, "breakpoint already exists").into(),
+            );
+        }
+
+        let exec = Process::open(self.id)?.regions()?.any(|region| {
+            region.is_ok_and(|region| {
+                addr >= region.start() && addr < region.end() && region.permissions().exec()
+            })
+        });
+
+        if !exec {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "address is not in an executable region",
+            )
+            .into());
+        }
+
+        let mut original = [0];
+
+        self.read_memory(&mut original, addr)?;
+        self.write_memory(&[INT3], addr)?;
+
+        self.breakpoints
+            .insert(addr, Breakpoint::new(addr, original[0], auto_continue));
+
+        Ok(())
+    }
+
+    pub fn remove_breakpoint(&mut self, addr: usize) -> Result<()> {
+        let breakpoint = self
+            .breakpoints
+            .remove(&addr)
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "breakpoint not found"))?;
+
+        self.write_memory(&[breakpoint.original()], addr)
+    }
+
+    pub fn breakpoints(&self) -> Vec<Breakpoint> {
+        self.breakpoints.values().cloned().collect()
+    }
+
+    pub fn resume_held(&mut self) {
+        let held: Vec<_> = self
+            .tasks
+            .iter()
+            .filter(|(_, task)| task.held)
+            .map(|(&tid, _)| tid)
+            .collect();
+
+        for tid in held {
+            self.tasks.get_mut(&tid).unwrap().held = false;
+            self.step_over(tid);
+        }
+    }
+
+    fn trap(&mut self, tid: libc::pid_t) -> bool {
+        let watched = self.watch_trap(tid);
+        let broke = self.break_trap(tid);
+
+        watched || broke
+    }
+
+    fn break_trap(&mut self, tid: libc::pid_t) -> bool {
+        let addr = match peek_user(tid, RIP) {
+            Ok(ip) => ip.wrapping_sub(1),
+            Err(_) => return false,
+        };
+
+        if !self.breakpoints.contains_key(&addr) || poke_user(tid, RIP, addr).is_err() {
+            return false;
+        }
+
+        let registers = self.registers(tid);
+        let breakpoint = self.breakpoints.get_mut(&addr).unwrap();
+
+        breakpoint.hit(tid as u32, registers.ok());
+
+        if breakpoint.auto_continue() {
+            self.step_over(tid);
+        } else {
+            self.tasks.get_mut(&tid).unwrap().held = true;
+        }
+
+        true
+    }
+
+    fn step_over(&mut self, tid: libc::pid_t) {
+        let addr = match peek_user(tid, RIP) {
+            Ok(addr) => addr,
+            Err(_) => return,
+        };
+
+        let original = match self.breakpoints.get(&addr) {
+            Some(breakpoint) => breakpoint.original(),
+            None => return,
+        };
+
+        let running: Vec<_> = self
+            .tasks
+            .iter()
+            .filter(|(_, task)| !task.stopped)
+            .map(|(&tid, _)| tid)
+            .collect();
+
+        self.stop_all();
+        let _ = self.memory.write_all_at(&[original], addr as u64);
+
+        self.single_step(tid);
+
+        if self.breakpoints.contains_key(&addr) {
+            let _ = self.memory.write_all_at(&[INT3], addr as u64);
+        }
+
+        for tid in running {
+            self.resume(tid);
+        }
+    }
+
+    fn single_step(&mut self, tid: libc::pid_t) -> bool {
+        while ptrace(libc::PTRACE_SINGLESTEP, tid, 0, 0).is_ok() {
+            let status = match wait(tid, 0) {
+                Ok(Some(status)) if libc::WIFSTOPPED(status) => status,
+                _ => break,
+            };
+
+            let signal = libc::WSTOPSIG(status);
+
+            if status >> 16 != 0 {
+                self.event(tid, status);
+            } else if signal == libc::SIGTRAP {
+                self.watch_trap(tid);
+                return true;
+            } else if let Some(task) = self.tasks.get_mut(&tid) {
+                task.signal = signal;
+            }
+        }
+
+        self.tasks.remove(&tid);
+        false
+    }
+
+    fn watch_trap(&mut self, tid: libc::pid_t) -> bool {
+        let dr6 = match peek_user(tid, debug_register(6)) {
+            Ok(dr6) => dr6,
+            Err(_) => return false,
+        };
+
+        if dr6 & ((1 << DEBUG_REGISTERS) - 1) == 0 {
+            return false;
+        }
+
+        if let Ok(regs) = get_regs(tid) {
+            let next_ip = regs.rip as usize;
+            let code = self.code_before(next_ip);
+
+            for (i, watchpoint) in self.watchpoints.iter_mut().enumerate() {
+                if let Some(watchpoint) = watchpoint.as_mut().filter(|_| dr6 & 1 << i != 0) {
+                    let range = watchpoint.addr()..watchpoint.addr() + watchpoint.size();
+                    let ip = accessor(&code, &regs, &range).unwrap_or(next_ip);
+
+                    watchpoint.hit(ip);
+                }
+            }
+        }
+
+        let _ = poke_user(tid, debug_register(6), 0);
+        true
+    }
+
+    fn code_before(&self, addr: usize) -> Vec<u8> {
+        let page = addr & !(memory::page_size() - 1);
+
+        for start in [addr.saturating_sub(MAX_INSTRUCTION_SIZE), page] {
+            let mut code = vec![0; addr - start];
+
+            if self.read_memory(&mut code, start).is_ok() {
+                for (&bp, breakpoint) in self.breakpoints.range(start..addr) {
+                    code[bp - start] = breakpoint.original();
+                }
+
+                return code;
+            }
+        }
+
+        Vec::new()
+    }
+
+    fn task(&self, tid: libc::pid_t) -> Result<&Task> {
+        self.tasks
+            .get(&tid)
+            .filter(|task| task.stopped)
+            .ok_or_else(thread::thread_gone)
+    }
+
+    fn stop_all(&mut self) {
+        loop {
+            let running: Vec<_> = self
+                .tasks
+                .iter()
+                .filter(|(_, task)| !task.stopped)
+                .map(|(&tid, _)| tid)
+                .collect();
+
+            if running.is_empty() {
+                break;
+            }
+
+            for &tid in &running {
+                let _ = ptrace(libc::PTRACE_INTERRUPT, tid, 0, 0);
+            }
+
+            for tid in running {
+                while self.tasks.get(&tid).is_some_and(|task| !task.stopped) {
+                    match wait(tid, 0) {
+                        Ok(Some(status)) => self.event(tid, status),
+                        Ok(None) => {}
+                        Err(_) => {
+                            self.tasks.remove(&tid);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn resume_all(&mut self) {
+        let stopped: Vec<_> = self
+            .tasks
+            .iter()
+            .filter(|(_, task)| task.stopped)
+            .map(|(&tid, _)| tid)
+            .collect();
+
+        for tid in stopped {
+            self.resume(tid);
+        }
+    }
+
+    fn resume(&mut self, tid: libc::pid_t) {
+        if let Some(task) = self.tasks.get_mut(&tid).filter(|task| !task.held) {
+            let _ = if task.listening {
+                ptrace(libc::PTRACE_LISTEN, tid, 0, 0)
+            } else {
+                ptrace(libc::PTRACE_CONT, tid, 0, task.signal as usize)
+            };
+
+            task.stopped = false;
+            task.listening = false;
+            task.signal = 0;
+        }
+    }
+
+    fn poll(&mut self) {
+        let running: Vec<_> = self
+            .tasks
+            .iter()
+            .filter(|(_, task)| !task.stopped)
+            .map(|(&tid, _)| tid)
+            .collect();
+
+        for tid in running {
+            match wait(tid, libc::WNOHANG) {
+                Ok(Some(status)) => {
+                    self.event(tid, status);
+                    self.resume(tid);
+                }
+                Ok(None) => {}
+                Err(_) => {
+                    self.tasks.remove(&tid);
+                }
+            }
+        }
+    }
+
+    fn event(&mut self, tid: libc::pid_t, status: libc::c_int) {
+        if libc::WIFEXITED(status) || libc::WIFSIGNALED(status) {
+            self.tasks.remove(&tid);
+            return;
+        }
+
+        let signal = libc::WSTOPSIG(status);
+        let starting = match self.tasks.get_mut(&tid) {
+            Some(task) => {
+                task.stopped = true;
+                mem::take(&mut task.starting)
+            }
+            None => return,
+        };
+
+        match status >> 16 {
+            libc::PTRACE_EVENT_STOP => {
+                if starting {
+                    let _ = self.set_debug_registers(tid);
+                }
+
+                self.tasks.get_mut(&tid).unwrap().listening = !starting
+                    && matches!(
+                        signal,
+                        libc::SIGSTOP | libc::SIGTSTP | libc::SIGTTIN | libc::SIGTTOU
+                    );
+            }
+            libc::PTRACE_EVENT_CLONE => {
+                let mut child: libc::c_ulong = 0;
+
+                if ptrace(
+                    libc::PTRACE_GETEVENTMSG,
+                    tid,
+                    0,
+                    &mut child as *mut _ as usize,
+                )
+                .is_ok()
+                {
+                    self.tasks.entry(child as libc::pid_t).or_insert(Task {
+                        starting: true,
+                        ..Task::default()
+                    });
+                }
+            }
+            0 if signal != libc::SIGTRAP || !self.trap(tid) => {
+                self.tasks.get_mut(&tid).unwrap().signal = signal;
+            }
+            _ => {}
+        }
+    }
+
+    fn detach(&mut self) {
+        self.stop_all();
+        self.watchpoints = Default::default();
+        let _ = self.update_debug_registers();
+
+        for (addr, breakpoint) in mem::take(&mut self.breakpoints) {
+            let _ = self
+                .memory
+                .write_all_at(&[breakpoint.original()], addr as u64);
+        }
+
+        for (&tid, task) in &self.tasks {
+            let _ = ptrace(libc::PTRACE_DETACH, tid, 0, task.signal as usize);
+        }
+
+        self.tasks.clear();
+    }
+}
+
+fn find(tracers: &mut Vec<(u32, Weak<Tracer>)>, id: u32) -> Option<Arc<Tracer>> {
+    tracers.retain(|(_, tracer)| tracer.strong_count() > 0);
+    tracers
+        .iter()
+        .find(|(tracer_id, _)| *tracer_id == id)
+        .and_then(|(_, tracer)| tracer.upgrade())
+}
+
+// Data breakpoints trap after the access, so find the instruction that ends
+// at rip and whose memory operand overlaps the watched range.
+fn accessor(code: &[u8], regs: &libc::user_regs_struct, range: &Range<usize>) -> Option<usize> {
+    let next_ip = regs.rip as usize;
+
+    (1..=code.len()).rev().find_map(|len| {
+        let ip = next_ip - len;
+        let mut decoder = Decoder::with_ip(
+            64,
+            &code[code.len() - len..],
+            ip as u64,
+            DecoderOptions::NONE,
+        );
+        let instruction = decoder.decode();
+
+        if instruction.is_invalid() || instruction.len() != len {
+            return None;
+        }
+
+        let size = instruction.memory_size().size();
+        let accessed = (0..instruction.op_count())
+            .filter(|&i| instruction.op_kind(i) == OpKind::Memory)
+            .filter_map(|i| instruction.virtual_address(i, 0, |reg, _, _| register(regs, reg)))
+            .map(|addr| addr as usize..addr as usize + size)
+            .any(|accessed| accessed.start < range.end && range.start < accessed.end);
+
+        accessed.then_some(ip)
+    })
+}
+
+fn register(regs: &libc::user_regs_struct, reg: Register) -> Option<u64> {
+    let value = match reg {
+        Register::RAX => regs.rax,
+        Register::RBX => regs.rbx,
+        Register::RCX => regs.rcx,
+        Register::RDX => regs.rdx,
+        Register::RSI => regs.rsi,
+        Register::RDI => regs.rdi,
+        Register::RBP => regs.rbp,
+        Register::RSP => regs.rsp,
+        Register::R8 => regs.r8,
+        Register::R9 => regs.r9,
+        Register::R10 => regs.r10,
+        Register::R11 => regs.r11,
+        Register::R12 => regs.r12,
+        Register::R13 => regs.r13,
+        Register::R14 => regs.r14,
+        Register::R15 => regs.r15,
+        Register::RIP => regs.rip,
+        Register::FS => regs.fs_base,
+        Register::GS => regs.gs_base,
+        Register::ES | Register::CS | Register::SS | Register::DS => 0,
+        _ => return None,
+    };
+
+    Some(value)
+}
+
+fn ptrace(
+    request: libc::c_uint,
+    tid: libc::pid_t,
+    addr: usize,
+    data: usize,
+) -> io::Result<libc::c_long> {
+    unsafe {
+        *libc::__errno_location() = 0;
+
+        let result = libc::ptrace(request, tid, addr, data);
+
+        if result == -1 && *libc::__errno_location() != 0 {
+            Err(io::Error::last_os_error())
+        } else {
+            Ok(result)
+        }
+    }
+}
+
+fn get_regs(tid: libc::pid_t) -> io::Result<libc::user_regs_struct> {
+    let mut regs: libc::user_regs_struct = unsafe { mem::zeroed() };
+
+    ptrace(libc::PTRACE_GETREGS, tid, 0, &mut regs as *mut _ as usize)?;
+    Ok(regs)
+}
+
+fn set_regs(tid: libc::pid_t, regs: &libc::user_regs_struct) -> io::Result<()> {
+    ptrace(libc::PTRACE_SETREGS, tid, 0, regs as *const _ as usize).map(|_| ())
+}
+
+fn peek_user(tid: libc::pid_t, offset: usize) -> io::Result<usize> {
+    ptrace(libc::PTRACE_PEEKUSER, tid, offset, 0).map(|value| value as usize)
+}
+
+fn poke_user(tid: libc::pid_t, offset: usize, value: usize) -> io::Result<()> {
+    ptrace(libc::PTRACE_POKEUSER, tid, offset, value).map(|_| ())
+}
+
+fn debug_register(i: usize) -> usize {
+    mem::offset_of!(libc::user, u_debugreg) + i * mem::size_of::<libc::c_ulonglong>()
+}
+
+fn wait(tid: libc::pid_t, options: libc::c_int) -> io::Result<Option<libc::c_int>> {
+    let mut status = 0;
+
+    loop {
+        match unsafe { libc::waitpid(tid, &mut status, options | libc::__WALL) } {
+            0 => return Ok(None),
+            -1 => {
+                let err = io::Error::last_os_error();
+
+                if err.kind() != io::ErrorKind::Interrupted {
+                    return Err(err);
+                }
+            }
+            _ => return Ok(Some(status)),
+        }
+    }
+}
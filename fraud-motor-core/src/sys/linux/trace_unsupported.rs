@@ -0,0 +1,94 @@
+use crate::debug::{Breakpoint, Watchpoint};
+use crate::error::Result;
+use crate::thread::Registers;
+use std::io;
+use std::ops::Range;
+use std::sync::Arc;
+
+pub enum Tracer {}
+
+pub enum Tracee {}
+
+impl Tracer {
+    pub fn attach(_id: u32) -> Result<Arc<Tracer>> {
+        Err(io::Error::from(io::ErrorKind::Unsupported).into())
+    }
+
+    pub fn find(_id: u32) -> Option<Arc<Tracer>> {
+        None
+    }
+
+    pub fn run<R, F>(&self, _command: F) -> Result<R>
+    where
+        R: Send + 'static,
+        F: FnOnce(&mut Tracee) -> Result<R> + Send + 'static,
+    {
+        match *self {}
+    }
+}
+
+impl Tracee {
+    pub fn registers(&self, _tid: libc::pid_t) -> Result<Registers> {
+        match *self {}
+    }
+
+    pub fn set_registers(&self, _tid: libc::pid_t, _registers: &Registers) -> Result<()> {
+        match *self {}
+    }
+
+    pub fn syscall(&mut self, _nr: libc::c_long, _args: [u64; 6]) -> Result<u64> {
+        match *self {}
+    }
+
+    pub fn call(&mut self, _addr: usize, _args: &[u64]) -> Result<u64> {
+        match *self {}
+    }
+
+    pub fn suspend(&mut self) {
+        match *self {}
+    }
+
+    pub fn unsuspend(&mut self) {
+        match *self {}
+    }
+
+    pub fn read_memory(&self, _buf: &mut [u8], _addr: usize) -> Result<()> {
+        match *self {}
+    }
+
+    pub fn write_memory(&self, _buf: &[u8], _addr: usize) -> Result<()> {
+        match *self {}
+    }
+
+    pub fn write_stopped(&self, _buf: &[u8], _addr: usize, _busy: &[Range<usize>]) -> Result<()> {
+        match *self {}
+    }
+
+    pub fn set_watchpoint(&mut self, _watchpoint: Watchpoint) -> Result<usize> {
+        match *self {}
+    }
+
+    pub fn remove_watchpoint(&mut self, _id: usize) -> Result<()> {
+        match *self {}
+    }
+
+    pub fn watchpoints(&self) -> Vec<(usize, Watchpoint)> {
+        match *self {}
+    }
+
+    pub fn add_breakpoint(&mut self, _addr: usize, _auto_continue: bool) -> Result<()> {
+        match *self {}
+    }
+
+    pub fn remove_breakpoint(&mut self, _addr: usize) -> Result<()> {
+        match *self {}
+    }
+
+    pub fn breakpoints(&self) -> Vec<Breakpoint> {
+        match *self {}
+    }
+
+    pub fn resume_held(&mut self) {
+        match *self {}
+    }
+}
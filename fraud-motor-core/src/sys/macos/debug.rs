@@ -0,0 +1,38 @@
+use crate::debug::{Breakpoint, Watchpoint};
+use crate::error::Result;
+
+pub struct Debugger;
+
+impl Debugger {
+    pub fn attach(id: u32) -> Result<Debugger> {
+        unimplemented!()
+    }
+
+    pub fn watch(&self, watchpoint: Watchpoint) -> Result<usize> {
+        unimplemented!()
+    }
+
+    pub fn unwatch(&self, id: usize) -> Result<()> {
+        unimplemented!()
+    }
+
+    pub fn watchpoints(&self) -> Result<Vec<(usize, Watchpoint)>> {
+        unimplemented!()
+    }
+
+    pub fn add_breakpoint(&self, addr: usize, auto_continue: bool) -> Result<()> {
+        unimplemented!()
+    }
+
+    pub fn remove_breakpoint(&self, addr: usize) -> Result<()> {
+        unimplemented!()
+    }
+
+    pub fn breakpoints(&self) -> Result<Vec<Breakpoint>> {
+        unimplemented!()
+    }
+
+    pub fn resume(&self) -> Result<()> {
+        unimplemented!()
+    }
+}
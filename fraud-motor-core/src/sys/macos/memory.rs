@@ -1,19 +1,24 @@
-use std::io;
+use crate::error::Result;
+use crate::memory::Backend;
 
 pub struct Memory;
 
 pub struct Options;
 
 impl Memory {
-    pub fn open(id: u32, options: &Options) -> io::Result<Memory> {
+    pub fn open(id: u32, options: &Options) -> Result<Memory> {
         unimplemented!()
     }
 
-    pub fn read(&self, buf: &mut [u8], addr: usize) -> io::Result<()> {
+    pub fn read(&self, buf: &mut [u8], addr: usize) -> Result<()> {
         unimplemented!()
     }
 
-    pub fn write(&self, buf: &[u8], addr: usize) -> io::Result<()> {
+    pub fn read_many(&self, reads: &mut [(usize, &mut [u8])]) -> Vec<Result<()>> {
+        unimplemented!()
+    }
+
+    pub fn write(&self, buf: &[u8], addr: usize) -> Result<()> {
         unimplemented!()
     }
 }
@@ -30,4 +35,12 @@ impl Options {
     pub fn write(&mut self, write: bool) -> &mut Options {
         unimplemented!()
     }
+
+    pub fn backend(&mut self, backend: Backend) -> &mut Options {
+        unimplemented!()
+    }
+}
+
+pub fn page_size() -> usize {
+    unimplemented!()
 }
@@ -1,2 +1,4 @@
+pub mod debug;
 pub mod memory;
 pub mod process;
+pub mod thread;
@@ -1,6 +1,10 @@
-use std::io;
+use crate::error::Result;
+use crate::process::{RegionKind, State};
+use crate::sys::macos::thread::Threads;
 use std::marker::PhantomData;
+use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
 pub struct List;
 
@@ -10,36 +14,93 @@ pub struct Regions<'a> {
     phantom: PhantomData<&'a Process>,
 }
 
+#[derive(Clone)]
 pub struct Region;
 
+pub struct Suspended<'a> {
+    phantom: PhantomData<&'a Process>,
+}
+
+#[derive(Clone)]
 pub struct Permissions;
 
+pub struct ProcessInfo;
+
 impl Iterator for List {
-    type Item = io::Result<u32>;
+    type Item = Result<u32>;
 
-    fn next(&mut self) -> Option<io::Result<u32>> {
+    fn next(&mut self) -> Option<Result<u32>> {
         unimplemented!()
     }
 }
 
 impl Process {
-    pub fn open(id: u32) -> io::Result<Process> {
+    pub fn open(id: u32) -> Result<Process> {
+        unimplemented!()
+    }
+
+    pub fn id(&self) -> u32 {
+        unimplemented!()
+    }
+
+    pub fn regions(&self) -> Result<Regions<'_>> {
+        unimplemented!()
+    }
+
+    pub fn path(&self) -> Result<PathBuf> {
+        unimplemented!()
+    }
+
+    pub fn threads(&self) -> Result<Threads<'_>> {
+        unimplemented!()
+    }
+
+    pub fn suspend(&self) -> Result<Suspended<'_>> {
+        unimplemented!()
+    }
+
+    pub fn alloc(
+        &self,
+        addr: Option<usize>,
+        size: usize,
+        permissions: &Permissions,
+    ) -> Result<usize> {
+        unimplemented!()
+    }
+
+    pub fn free(&self, addr: usize, size: usize) -> Result<()> {
         unimplemented!()
     }
 
-    pub fn regions(&self) -> io::Result<Regions> {
+    pub fn protect(&self, addr: usize, size: usize, permissions: &Permissions) -> Result<()> {
         unimplemented!()
     }
 
-    pub fn path(&self) -> io::Result<PathBuf> {
+    pub fn write_stopped(&self, buf: &[u8], addr: usize, busy: &[Range<usize>]) -> Result<()> {
+        unimplemented!()
+    }
+
+    pub fn call(&self, addr: usize, args: &[u64]) -> Result<u64> {
+        unimplemented!()
+    }
+
+    pub fn inject_library(&self, path: &Path) -> Result<usize> {
+        unimplemented!()
+    }
+
+    pub fn unload_library(&self, handle: usize) -> Result<()> {
+        unimplemented!()
+    }
+
+    pub fn info(&self) -> Result<ProcessInfo> {
         unimplemented!()
     }
 }
 
 impl<'a> Iterator for Regions<'a> {
-    type Item = io::Result<Region>;
+    type Item = Result<Region>;
 
-    fn next(&mut self) -> Option<io::Result<Region>> {
+    fn next(&mut self) -> Option<Result<Region>> {
         unimplemented!()
     }
 }
@@ -57,12 +118,40 @@ impl Region {
         unimplemented!()
     }
 
+    pub fn shared(&self) -> bool {
+        unimplemented!()
+    }
+
+    pub fn offset(&self) -> u64 {
+        unimplemented!()
+    }
+
+    pub fn device(&self) -> (u32, u32) {
+        unimplemented!()
+    }
+
+    pub fn inode(&self) -> u64 {
+        unimplemented!()
+    }
+
+    pub fn kind(&self) -> RegionKind {
+        unimplemented!()
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        unimplemented!()
+    }
+
     pub fn path(&self) -> Option<&Path> {
         unimplemented!()
     }
 }
 
 impl Permissions {
+    pub fn new(read: bool, write: bool, exec: bool) -> Permissions {
+        unimplemented!()
+    }
+
     pub fn read(&self) -> bool {
         unimplemented!()
     }
@@ -76,6 +165,40 @@ impl Permissions {
     }
 }
 
-pub fn list() -> io::Result<List> {
+impl ProcessInfo {
+    pub fn id(&self) -> u32 {
+        unimplemented!()
+    }
+
+    pub fn name(&self) -> &str {
+        unimplemented!()
+    }
+
+    pub fn cmdline(&self) -> &[String] {
+        unimplemented!()
+    }
+
+    pub fn parent_id(&self) -> u32 {
+        unimplemented!()
+    }
+
+    pub fn uid(&self) -> u32 {
+        unimplemented!()
+    }
+
+    pub fn state(&self) -> State {
+        unimplemented!()
+    }
+
+    pub fn start_time(&self) -> SystemTime {
+        unimplemented!()
+    }
+
+    pub fn rss(&self) -> usize {
+        unimplemented!()
+    }
+}
+
+pub fn list() -> Result<List> {
     unimplemented!()
 }
@@ -0,0 +1,45 @@
+use crate::error::Result;
+use crate::process::State;
+use crate::sys::macos::process::Process;
+use crate::thread::Registers;
+use std::marker::PhantomData;
+
+pub struct Threads<'a> {
+    phantom: PhantomData<&'a Process>,
+}
+
+pub struct Thread;
+
+impl<'a> Iterator for Threads<'a> {
+    type Item = Result<Thread>;
+
+    fn next(&mut self) -> Option<Result<Thread>> {
+        unimplemented!()
+    }
+}
+
+impl Thread {
+    pub fn id(&self) -> u32 {
+        unimplemented!()
+    }
+
+    pub fn name(&self) -> &str {
+        unimplemented!()
+    }
+
+    pub fn state(&self) -> State {
+        unimplemented!()
+    }
+
+    pub fn stack_pointer(&self) -> Option<usize> {
+        unimplemented!()
+    }
+
+    pub fn registers(&self) -> Result<Registers> {
+        unimplemented!()
+    }
+
+    pub fn set_registers(&self, registers: &Registers) -> Result<()> {
+        unimplemented!()
+    }
+}
@@ -1,34 +1,47 @@
+use crate::error::{Error, Result};
+use crate::memory::Backend;
 use crate::sys::windows::{self, api, Handle};
-use std::{io, ptr};
+use std::{io, mem, ptr};
 
 pub struct Memory(Handle);
 
 pub struct Options(api::DWORD);
 
 impl Memory {
-    pub fn open(id: u32, options: &Options) -> io::Result<Memory> {
+    pub fn open(id: u32, options: &Options) -> Result<Memory> {
         unsafe {
-            let handle = windows::check(api::OpenProcess(options.0, api::FALSE, id))?;
+            let handle = windows::check(api::OpenProcess(options.0, api::FALSE, id))
+                .map_err(windows::process_error)?;
 
             Ok(Memory(Handle(handle)))
         }
     }
 
-    pub fn read(&self, buf: &mut [u8], addr: usize) -> io::Result<()> {
+    pub fn read(&self, buf: &mut [u8], addr: usize) -> Result<()> {
+        let mut read = 0;
+
         unsafe {
             windows::check(api::ReadProcessMemory(
                 *self.0,
                 addr as api::LPCVOID,
                 buf.as_mut_ptr() as api::LPVOID,
                 buf.len(),
-                ptr::null_mut(),
-            ))?;
+                &mut read,
+            ))
+            .map_err(|err| mem_error(err, addr, read))?;
 
             Ok(())
         }
     }
 
-    pub fn write(&self, buf: &[u8], addr: usize) -> io::Result<()> {
+    pub fn read_many(&self, reads: &mut [(usize, &mut [u8])]) -> Vec<Result<()>> {
+        reads
+            .iter_mut()
+            .map(|(addr, buf)| self.read(buf, *addr))
+            .collect()
+    }
+
+    pub fn write(&self, buf: &[u8], addr: usize) -> Result<()> {
         unsafe {
             windows::check(api::WriteProcessMemory(
                 *self.0,
@@ -36,7 +49,8 @@ impl Memory {
                 buf.as_ptr() as api::LPCVOID,
                 buf.len(),
                 ptr::null_mut(),
-            ))?;
+            ))
+            .map_err(|err| mem_error(err, addr, 0))?;
 
             Ok(())
         }
@@ -59,4 +73,25 @@ impl Options {
         self.0 |= api::PROCESS_VM_WRITE * write as api::DWORD;
         self
     }
+
+    pub fn backend(&mut self, _backend: Backend) -> &mut Options {
+        self
+    }
+}
+
+pub fn page_size() -> usize {
+    unsafe {
+        let mut info = mem::zeroed::<api::SYSTEM_INFO>();
+
+        api::GetSystemInfo(&mut info);
+        info.dwPageSize as usize
+    }
+}
+
+fn mem_error(err: io::Error, addr: usize, read: usize) -> Error {
+    match err.raw_os_error().map(|code| code as api::DWORD) {
+        Some(api::ERROR_PARTIAL_COPY) if read > 0 => Error::PartialRead { addr, read },
+        Some(api::ERROR_PARTIAL_COPY | api::ERROR_NOACCESS) => Error::Unmapped(addr),
+        _ => err.into(),
+    }
 }
@@ -1,5 +1,7 @@
+pub mod debug;
 pub mod memory;
 pub mod process;
+pub mod thread;
 
 mod api {
     pub use winapi::shared::basetsd::*;
@@ -9,9 +11,11 @@ mod api {
     pub use winapi::um::memoryapi::*;
     pub use winapi::um::processthreadsapi::*;
     pub use winapi::um::psapi::*;
+    pub use winapi::um::sysinfoapi::*;
     pub use winapi::um::winnt::*;
 }
 
+use crate::error::Error as CoreError;
 use std::ops::Deref;
 use std::{io, ptr};
 
@@ -60,3 +64,10 @@ fn check<T: Error>(result: T) -> io::Result<T> {
         Err(io::Error::last_os_error())
     }
 }
+
+fn process_error(err: io::Error) -> CoreError {
+    match err.raw_os_error().map(|code| code as api::DWORD) {
+        Some(api::ERROR_INVALID_PARAMETER) => CoreError::ProcessGone,
+        _ => err.into(),
+    }
+}
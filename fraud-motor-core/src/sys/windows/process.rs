@@ -1,7 +1,12 @@
+use crate::error::Result;
+use crate::process::{RegionKind, State};
+use crate::sys::windows::thread::Threads;
 use crate::sys::windows::{self, api, Handle};
 use std::marker::PhantomData;
 use std::mem::{self, MaybeUninit};
+use std::ops::Range;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 use std::{io, iter, slice, str, vec};
 
 pub struct List(vec::IntoIter<api::DWORD>);
@@ -14,35 +19,48 @@ pub struct Regions<'a> {
     phantom: PhantomData<&'a Process>,
 }
 
+#[derive(Clone)]
 pub struct Region {
     info: api::MEMORY_BASIC_INFORMATION,
     path: Option<PathBuf>,
 }
 
+pub struct Suspended<'a> {
+    phantom: PhantomData<&'a Process>,
+}
+
+#[derive(Clone)]
 pub struct Permissions(api::DWORD);
 
+pub enum ProcessInfo {}
+
 impl Iterator for List {
-    type Item = io::Result<u32>;
+    type Item = Result<u32>;
 
-    fn next(&mut self) -> Option<io::Result<u32>> {
+    fn next(&mut self) -> Option<Result<u32>> {
         self.0.next().map(Ok)
     }
 }
 
 impl Process {
-    pub fn open(id: u32) -> io::Result<Process> {
+    pub fn open(id: u32) -> Result<Process> {
         unsafe {
             let handle = windows::check(api::OpenProcess(
                 api::PROCESS_QUERY_INFORMATION,
                 api::FALSE,
                 id,
-            ))?;
+            ))
+            .map_err(windows::process_error)?;
 
             Ok(Process(Handle(handle)))
         }
     }
 
-    pub fn regions(&self) -> io::Result<Regions> {
+    pub fn id(&self) -> u32 {
+        unsafe { api::GetProcessId(*self.0) }
+    }
+
+    pub fn regions(&self) -> Result<Regions<'_>> {
         Ok(Regions {
             handle: *self.0,
             addr: Some(0),
@@ -50,7 +68,7 @@ impl Process {
         })
     }
 
-    pub fn path(&self) -> io::Result<PathBuf> {
+    pub fn path(&self) -> Result<PathBuf> {
         unsafe {
             let mut buf: [MaybeUninit<u8>; api::MAX_PATH] = MaybeUninit::uninit().assume_init();
 
@@ -65,12 +83,57 @@ impl Process {
             Ok(str::from_utf8_unchecked(buf).into())
         }
     }
+
+    pub fn threads(&self) -> Result<Threads<'_>> {
+        Err(io::Error::from(io::ErrorKind::Unsupported).into())
+    }
+
+    pub fn suspend(&self) -> Result<Suspended<'_>> {
+        Err(io::Error::from(io::ErrorKind::Unsupported).into())
+    }
+
+    pub fn alloc(
+        &self,
+        addr: Option<usize>,
+        size: usize,
+        permissions: &Permissions,
+    ) -> Result<usize> {
+        Err(io::Error::from(io::ErrorKind::Unsupported).into())
+    }
+
+    pub fn free(&self, addr: usize, size: usize) -> Result<()> {
+        Err(io::Error::from(io::ErrorKind::Unsupported).into())
+    }
+
+    pub fn protect(&self, addr: usize, size: usize, permissions: &Permissions) -> Result<()> {
+        Err(io::Error::from(io::ErrorKind::Unsupported).into())
+    }
+
+    pub fn write_stopped(&self, buf: &[u8], addr: usize, busy: &[Range<usize>]) -> Result<()> {
+        Err(io::Error::from(io::ErrorKind::Unsupported).into())
+    }
+
+    pub fn call(&self, addr: usize, args: &[u64]) -> Result<u64> {
+        Err(io::Error::from(io::ErrorKind::Unsupported).into())
+    }
+
+    pub fn inject_library(&self, path: &Path) -> Result<usize> {
+        Err(io::Error::from(io::ErrorKind::Unsupported).into())
+    }
+
+    pub fn unload_library(&self, handle: usize) -> Result<()> {
+        Err(io::Error::from(io::ErrorKind::Unsupported).into())
+    }
+
+    pub fn info(&self) -> Result<ProcessInfo> {
+        Err(io::Error::from(io::ErrorKind::Unsupported).into())
+    }
 }
 
 impl<'a> Iterator for Regions<'a> {
-    type Item = io::Result<Region>;
+    type Item = Result<Region>;
 
-    fn next(&mut self) -> Option<io::Result<Region>> {
+    fn next(&mut self) -> Option<Result<Region>> {
         iter::from_fn(|| {
             self.addr.map(|addr| unsafe {
                 let mut info = MaybeUninit::uninit();
@@ -92,7 +155,7 @@ impl<'a> Iterator for Regions<'a> {
             info.as_ref().err().and_then(io::Error::raw_os_error)
                 != Some(api::ERROR_INVALID_PARAMETER as i32)
         })
-        .map(|info| unsafe {
+        .map(|info| -> Result<Option<Region>> {
             let info = info?;
 
             if info.State & api::MEM_COMMIT == 0 {
@@ -100,19 +163,22 @@ impl<'a> Iterator for Regions<'a> {
             } else if info.Type & (api::MEM_IMAGE | api::MEM_MAPPED) == 0 {
                 Ok(Some(Region { info, path: None }))
             } else {
-                let mut buf: [MaybeUninit<u8>; api::MAX_PATH] = MaybeUninit::uninit().assume_init();
-
-                let size = windows::check(api::GetMappedFileNameA(
-                    self.handle,
-                    info.BaseAddress,
-                    buf.as_mut_ptr() as api::LPSTR,
-                    buf.len() as api::DWORD,
-                ))? as usize;
-
-                let buf = slice::from_raw_parts(buf.as_ptr() as *const u8, size);
-                let path = Some(str::from_utf8_unchecked(buf).into());
-
-                Ok(Some(Region { info, path }))
+                unsafe {
+                    let mut buf: [MaybeUninit<u8>; api::MAX_PATH] =
+                        MaybeUninit::uninit().assume_init();
+
+                    let size = windows::check(api::GetMappedFileNameA(
+                        self.handle,
+                        info.BaseAddress,
+                        buf.as_mut_ptr() as api::LPSTR,
+                        buf.len() as api::DWORD,
+                    ))? as usize;
+
+                    let buf = slice::from_raw_parts(buf.as_ptr() as *const u8, size);
+                    let path = Some(str::from_utf8_unchecked(buf).into());
+
+                    Ok(Some(Region { info, path }))
+                }
             }
         })
         .find_map(Result::transpose)
@@ -132,6 +198,34 @@ impl Region {
         Permissions(self.info.Protect)
     }
 
+    pub fn shared(&self) -> bool {
+        self.info.Type == api::MEM_MAPPED
+    }
+
+    pub fn offset(&self) -> u64 {
+        (self.info.BaseAddress as usize - self.info.AllocationBase as usize) as u64
+    }
+
+    pub fn device(&self) -> (u32, u32) {
+        (0, 0)
+    }
+
+    pub fn inode(&self) -> u64 {
+        0
+    }
+
+    pub fn kind(&self) -> RegionKind {
+        if self.path.is_some() {
+            RegionKind::File
+        } else {
+            RegionKind::Anonymous
+        }
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.path.as_deref().and_then(Path::to_str)
+    }
+
     pub fn path(&self) -> Option<&Path> {
         self.path.as_deref()
     }
@@ -144,6 +238,17 @@ impl Permissions {
     const WRITE: api::DWORD = api::PAGE_READWRITE | api::PAGE_WRITECOPY | Permissions::EXEC_WRITE;
     const READ: api::DWORD = api::PAGE_READONLY | Permissions::WRITE | Permissions::EXEC_READ;
 
+    pub fn new(read: bool, write: bool, exec: bool) -> Permissions {
+        Permissions(match (read || write, write, exec) {
+            (false, _, false) => api::PAGE_NOACCESS,
+            (true, false, false) => api::PAGE_READONLY,
+            (_, true, false) => api::PAGE_READWRITE,
+            (false, _, true) => api::PAGE_EXECUTE,
+            (true, false, true) => api::PAGE_EXECUTE_READ,
+            (_, true, true) => api::PAGE_EXECUTE_READWRITE,
+        })
+    }
+
     pub fn read(&self) -> bool {
         self.0 & Permissions::READ != 0
     }
@@ -157,7 +262,41 @@ impl Permissions {
     }
 }
 
-pub fn list() -> io::Result<List> {
+impl ProcessInfo {
+    pub fn id(&self) -> u32 {
+        match *self {}
+    }
+
+    pub fn name(&self) -> &str {
+        match *self {}
+    }
+
+    pub fn cmdline(&self) -> &[String] {
+        match *self {}
+    }
+
+    pub fn parent_id(&self) -> u32 {
+        match *self {}
+    }
+
+    pub fn uid(&self) -> u32 {
+        match *self {}
+    }
+
+    pub fn state(&self) -> State {
+        match *self {}
+    }
+
+    pub fn start_time(&self) -> SystemTime {
+        match *self {}
+    }
+
+    pub fn rss(&self) -> usize {
+        match *self {}
+    }
+}
+
+pub fn list() -> Result<List> {
     unsafe {
         let mut vec = vec![0; 1024];
 
@@ -0,0 +1,45 @@
+use crate::error::Result;
+use crate::process::State;
+use crate::sys::windows::process::Process;
+use crate::thread::Registers;
+use std::marker::PhantomData;
+
+pub struct Threads<'a> {
+    phantom: PhantomData<&'a Process>,
+}
+
+pub enum Thread {}
+
+impl<'a> Iterator for Threads<'a> {
+    type Item = Result<Thread>;
+
+    fn next(&mut self) -> Option<Result<Thread>> {
+        None
+    }
+}
+
+impl Thread {
+    pub fn id(&self) -> u32 {
+        match *self {}
+    }
+
+    pub fn name(&self) -> &str {
+        match *self {}
+    }
+
+    pub fn state(&self) -> State {
+        match *self {}
+    }
+
+    pub fn stack_pointer(&self) -> Option<usize> {
+        match *self {}
+    }
+
+    pub fn registers(&self) -> Result<Registers> {
+        match *self {}
+    }
+
+    pub fn set_registers(&self, registers: &Registers) -> Result<()> {
+        match *self {}
+    }
+}
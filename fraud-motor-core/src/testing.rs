@@ -0,0 +1,30 @@
+pub struct Child(libc::pid_t);
+
+impl Child {
+    pub fn fork(f: fn() -> !) -> Child {
+        match unsafe { libc::fork() } {
+            -1 => panic!("fork failed"),
+            0 => f(),
+            pid => Child(pid),
+        }
+    }
+
+    pub fn id(&self) -> u32 {
+        self.0 as u32
+    }
+}
+
+impl Drop for Child {
+    fn drop(&mut self) {
+        unsafe {
+            libc::kill(self.0, libc::SIGKILL);
+            libc::waitpid(self.0, &mut 0, 0);
+        }
+    }
+}
+
+pub fn idle() -> ! {
+    loop {
+        unsafe { libc::usleep(1000) };
+    }
+}
@@ -0,0 +1,255 @@
+use crate::error::Result;
+use crate::process::{Region, RegionKind, State};
+use crate::sys::thread as thread_imp;
+
+pub struct Threads<'a> {
+    threads: thread_imp::Threads<'a>,
+    regions: Vec<Region>,
+    id: u32,
+}
+
+pub struct Thread {
+    thread: thread_imp::Thread,
+    stack: Option<Region>,
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Registers {
+    pub rax: u64,
+    pub rbx: u64,
+    pub rcx: u64,
+    pub rdx: u64,
+    pub rsi: u64,
+    pub rdi: u64,
+    pub rbp: u64,
+    pub rsp: u64,
+    pub r8: u64,
+    pub r9: u64,
+    pub r10: u64,
+    pub r11: u64,
+    pub r12: u64,
+    pub r13: u64,
+    pub r14: u64,
+    pub r15: u64,
+    pub rip: u64,
+    pub rflags: u64,
+    pub fs_base: u64,
+    pub gs_base: u64,
+}
+
+impl<'a> Threads<'a> {
+    pub(crate) fn new(threads: thread_imp::Threads<'a>, regions: Vec<Region>, id: u32) -> Self {
+        Threads {
+            threads,
+            regions,
+            id,
+        }
+    }
+}
+
+impl<'a> Iterator for Threads<'a> {
+    type Item = Result<Thread>;
+
+    fn next(&mut self) -> Option<Result<Thread>> {
+        self.threads.next().map(|thread| {
+            let thread = thread?;
+            let stack = match thread.stack_pointer() {
+                Some(sp) => self
+                    .regions
+                    .iter()
+                    .find(|region| sp >= region.start() && sp < region.end()),
+                None if thread.id() == self.id => self
+                    .regions
+                    .iter()
+                    .find(|region| region.kind() == RegionKind::Stack),
+                None => None,
+            };
+
+            Ok(Thread {
+                stack: stack.cloned(),
+                thread,
+            })
+        })
+    }
+}
+
+impl Thread {
+    pub fn id(&self) -> u32 {
+        self.thread.id()
+    }
+
+    pub fn name(&self) -> &str {
+        self.thread.name()
+    }
+
+    pub fn state(&self) -> State {
+        self.thread.state()
+    }
+
+    pub fn stack(&self) -> Option<&Region> {
+        self.stack.as_ref()
+    }
+
+    pub fn registers(&self) -> Result<Registers> {
+        self.thread.registers()
+    }
+
+    pub fn set_registers(&self, registers: &Registers) -> Result<()> {
+        self.thread.set_registers(registers)
+    }
+}
+
+impl Registers {
+    pub const NAMES: [&'static str; 20] = [
+        "rax", "rbx", "rcx", "rdx", "rsi", "rdi", "rbp", "rsp", "r8", "r9", "r10", "r11", "r12",
+        "r13", "r14", "r15", "rip", "rflags", "fs_base", "gs_base",
+    ];
+
+    pub fn get(&self, name: &str) -> Option<u64> {
+        let mut registers = *self;
+
+        registers.get_mut(name).copied()
+    }
+
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut u64> {
+        match name {
+            "rax" => Some(&mut self.rax),
+            "rbx" => Some(&mut self.rbx),
+            "rcx" => Some(&mut self.rcx),
+            "rdx" => Some(&mut self.rdx),
+            "rsi" => Some(&mut self.rsi),
+            "rdi" => Some(&mut self.rdi),
+            "rbp" => Some(&mut self.rbp),
+            "rsp" => Some(&mut self.rsp),
+            "r8" => Some(&mut self.r8),
+            "r9" => Some(&mut self.r9),
+            "r10" => Some(&mut self.r10),
+            "r11" => Some(&mut self.r11),
+            "r12" => Some(&mut self.r12),
+            "r13" => Some(&mut self.r13),
+            "r14" => Some(&mut self.r14),
+            "r15" => Some(&mut self.r15),
+            "rip" => Some(&mut self.rip),
+            "rflags" => Some(&mut self.rflags),
+            "fs_base" => Some(&mut self.fs_base),
+            "gs_base" => Some(&mut self.gs_base),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::process::Process;
+    #[cfg(target_arch = "x86_64")]
+    use crate::testing::Child;
+    use std::sync::mpsc;
+    use std::time::Duration;
+    use std::{process, ptr, thread};
+
+    #[test]
+    fn test_threads() {
+        let (sender, receiver) = mpsc::channel();
+        let (done, wait) = mpsc::channel::<()>();
+
+        let handle = thread::Builder::new()
+            .name("fm-test-thread".to_owned())
+            .spawn(move || {
+                let local = 0u8;
+
+                sender.send(ptr::addr_of!(local) as usize).unwrap();
+                wait.recv().unwrap();
+            })
+            .unwrap();
+
+        let local = receiver.recv().unwrap();
+        let proc = Process::open(process::id()).unwrap();
+        let threads: Vec<_> = proc.threads().unwrap().collect::<Result<_>>().unwrap();
+
+        let main = threads.iter().find(|t| t.id() == process::id()).unwrap();
+        assert!(main.stack().is_some());
+
+        let thread = loop {
+            let thread = proc
+                .threads()
+                .unwrap()
+                .map(Result::unwrap)
+                .find(|t| t.name() == "fm-test-thread")
+                .unwrap();
+
+            if thread.state() == State::Sleeping {
+                break thread;
+            }
+
+            thread::sleep(Duration::from_millis(1));
+        };
+
+        let stack = thread.stack().unwrap();
+        assert!(local >= stack.start() && local < stack.end());
+
+        done.send(()).unwrap();
+        handle.join().unwrap();
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    fn spin() -> ! {
+        thread::spawn(|| loop {
+            std::hint::spin_loop();
+        });
+
+        loop {
+            std::hint::spin_loop();
+        }
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn test_thread_stack_running() {
+        let child = Child::fork(spin);
+        let proc = Process::open(child.id()).unwrap();
+
+        let threads = loop {
+            let threads: Vec<_> = proc.threads().unwrap().collect::<Result<_>>().unwrap();
+
+            if threads.len() == 2 {
+                break threads;
+            }
+
+            thread::sleep(Duration::from_millis(1));
+        };
+
+        assert!(threads.iter().all(|thread| thread.stack().is_some()));
+    }
+
+    #[cfg(target_arch = "x86_64")]
+    #[test]
+    fn test_thread_registers() {
+        let mut child = process::Command::new("sleep").arg("10").spawn().unwrap();
+        let proc = Process::open(child.id()).unwrap();
+
+        while proc.info().unwrap().state() != State::Sleeping {
+            thread::sleep(Duration::from_millis(1));
+        }
+
+        let thread = proc.threads().unwrap().next().unwrap().unwrap();
+        let mut registers = thread.registers().unwrap();
+        let stack = thread.stack().unwrap();
+
+        assert_ne!(registers.rip, 0);
+        assert!(registers.rsp as usize >= stack.start() && (registers.rsp as usize) < stack.end());
+        assert_eq!(registers.get("rsp"), Some(registers.rsp));
+
+        let rbx = registers.rbx;
+        *registers.get_mut("rbx").unwrap() = 0x1337;
+        thread.set_registers(&registers).unwrap();
+        assert_eq!(thread.registers().unwrap().rbx, 0x1337);
+
+        registers.rbx = rbx;
+        thread.set_registers(&registers).unwrap();
+        assert_eq!(thread.registers().unwrap(), registers);
+
+        child.kill().unwrap();
+        child.wait().unwrap();
+    }
+}
@@ -0,0 +1,182 @@
+use crate::error::Result;
+use std::cmp::Ordering;
+use std::fmt;
+use std::io;
+use std::str::FromStr;
+
+macro_rules! values {
+    ($($variant:ident $ty:ident $name:literal,)*) => {
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+        pub enum Type {
+            $($variant,)*
+        }
+
+        #[derive(Clone, Copy, Debug, PartialEq)]
+        pub enum Value {
+            $($variant($ty),)*
+        }
+
+        impl Type {
+            pub fn size(&self) -> usize {
+                match self {
+                    $(Type::$variant => std::mem::size_of::<$ty>(),)*
+                }
+            }
+
+            pub fn parse(&self, text: &str) -> Result<Value> {
+                match self {
+                    $(Type::$variant => text.parse().map(Value::$variant).map_err(|_| invalid(text)),)*
+                }
+            }
+
+            pub fn from_bytes(&self, bytes: &[u8]) -> Value {
+                match self {
+                    $(Type::$variant => Value::$variant($ty::from_ne_bytes(
+                        bytes[..std::mem::size_of::<$ty>()].try_into().unwrap(),
+                    )),)*
+                }
+            }
+
+            pub fn from_be_bytes(&self, bytes: &[u8]) -> Value {
+                match self {
+                    $(Type::$variant => Value::$variant($ty::from_be_bytes(
+                        bytes[..std::mem::size_of::<$ty>()].try_into().unwrap(),
+                    )),)*
+                }
+            }
+        }
+
+        impl Value {
+            pub fn ty(&self) -> Type {
+                match self {
+                    $(Value::$variant(_) => Type::$variant,)*
+                }
+            }
+
+            pub fn to_bytes(&self) -> Vec<u8> {
+                match self {
+                    $(Value::$variant(value) => value.to_ne_bytes().to_vec(),)*
+                }
+            }
+
+            pub fn to_be_bytes(&self) -> Vec<u8> {
+                match self {
+                    $(Value::$variant(value) => value.to_be_bytes().to_vec(),)*
+                }
+            }
+        }
+
+        impl PartialOrd for Value {
+            fn partial_cmp(&self, other: &Value) -> Option<Ordering> {
+                match (self, other) {
+                    $((Value::$variant(a), Value::$variant(b)) => a.partial_cmp(b),)*
+                    _ => None,
+                }
+            }
+        }
+
+        impl FromStr for Type {
+            type Err = io::Error;
+
+            fn from_str(s: &str) -> std::result::Result<Type, io::Error> {
+                match s {
+                    $($name => Ok(Type::$variant),)*
+                    _ => Err(io::Error::new(io::ErrorKind::InvalidInput, "unknown value type")),
+                }
+            }
+        }
+
+        impl fmt::Display for Type {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    $(Type::$variant => write!(f, $name),)*
+                }
+            }
+        }
+
+        impl fmt::Display for Value {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                match self {
+                    $(Value::$variant(value) => write!(f, "{}", value),)*
+                }
+            }
+        }
+    };
+}
+
+values! {
+    U8 u8 "u8",
+    U16 u16 "u16",
+    U32 u32 "u32",
+    U64 u64 "u64",
+    U128 u128 "u128",
+    I8 i8 "i8",
+    I16 i16 "i16",
+    I32 i32 "i32",
+    I64 i64 "i64",
+    I128 i128 "i128",
+    F32 f32 "f32",
+    F64 f64 "f64",
+}
+
+fn invalid(text: &str) -> crate::error::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("invalid value: {}", text),
+    )
+    .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_value() {
+        let ty: Type = "i16".parse().unwrap();
+        let value = ty.parse("-2").unwrap();
+
+        assert_eq!(value, Value::I16(-2));
+        assert_eq!(ty.size(), 2);
+        assert_eq!(ty.to_string(), "i16");
+        assert_eq!(value.to_bytes(), (-2i16).to_ne_bytes());
+        assert_eq!(ty.from_bytes(&value.to_bytes()), value);
+        assert!(value < Value::I16(3));
+        assert_eq!(value.partial_cmp(&Value::I32(3)), None);
+        assert_eq!(Type::F32.parse("1.5").unwrap().to_string(), "1.5");
+        assert!(Type::U8.parse("256").is_err());
+        assert!("u7".parse::<Type>().is_err());
+    }
+
+    #[test]
+    fn test_value_wide() {
+        let max = Type::U128.parse(&u128::MAX.to_string()).unwrap();
+        let min = Type::I128.parse(&i128::MIN.to_string()).unwrap();
+
+        assert_eq!(max, Value::U128(u128::MAX));
+        assert_eq!(min, Value::I128(i128::MIN));
+        assert_eq!(Type::U128.size(), 16);
+        assert_eq!(Type::I128.to_string(), "i128");
+        assert_eq!(Type::U128.from_bytes(&max.to_bytes()), max);
+        assert_eq!(Type::I128.from_bytes(&min.to_bytes()), min);
+        assert_eq!(min.to_string(), i128::MIN.to_string());
+        assert!(Type::U128.parse("-1").is_err());
+        assert!(min < Value::I128(0));
+    }
+
+    #[test]
+    fn test_value_big_endian() {
+        let value = Value::U32(0x1234_5678);
+        let wide = Value::I128(-0x0102_0304);
+
+        assert_eq!(value.to_be_bytes(), [0x12, 0x34, 0x56, 0x78]);
+        assert_eq!(Type::U32.from_be_bytes(&[0x12, 0x34, 0x56, 0x78]), value);
+        assert_eq!(Type::U32.from_be_bytes(&value.to_be_bytes()), value);
+        assert_eq!(Type::I128.from_be_bytes(&wide.to_be_bytes()), wide);
+        assert_eq!(
+            Type::F64.from_be_bytes(&Value::F64(1.5).to_be_bytes()),
+            Value::F64(1.5)
+        );
+        assert_eq!(wide.to_be_bytes()[15], 0xfc);
+    }
+}